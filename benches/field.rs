@@ -0,0 +1,82 @@
+// Field access benchmarks over the grid puzzle inputs
+// run with `cargo bench --bench field`
+use advent2024::field::Field;
+use advent2024::location::Location;
+use std::hint::black_box;
+use std::time::{Duration, Instant};
+
+const ROUNDS: u32 = 200;
+
+fn bench<T>(name: &str, field: &Field<T>, run: impl Fn(&Field<T>) -> usize) {
+    // warm up caches before timing
+    black_box(run(field));
+    let t = Instant::now();
+    (0..ROUNDS).for_each(|_| {
+        black_box(run(black_box(field)));
+    });
+    let elapsed: Duration = t.elapsed() / ROUNDS;
+    println!(
+        "{name:<28} {:>4}x{:<4} {elapsed:>12.2?}/iter",
+        field.width(),
+        field.height()
+    );
+}
+
+// visit every cell by location, row major
+fn scan_rows<T: PartialEq>(field: &Field<T>, val: &T) -> usize {
+    (0..field.height())
+        .flat_map(|y| (0..field.width()).map(move |x| Location(x, y)))
+        .filter(|&l| field.get(l) == Some(val))
+        .count()
+}
+
+// visit every cell by location, column major
+fn scan_columns<T: PartialEq>(field: &Field<T>, val: &T) -> usize {
+    (0..field.width())
+        .flat_map(|x| (0..field.height()).map(move |y| Location(x, y)))
+        .filter(|&l| field.get(l) == Some(val))
+        .count()
+}
+
+// peek all four neighbours of every cell
+fn peek_neighbours<T: PartialEq>(field: &Field<T>, val: &T) -> usize {
+    (0..field.height())
+        .flat_map(|y| (0..field.width()).map(move |x| Location(x, y)))
+        .map(|l| {
+            [(0, 1), (0, -1), (1, 0), (-1, 0)]
+                .into_iter()
+                .filter(|&d| field.peek(l, d) == Some(val))
+                .count()
+        })
+        .sum()
+}
+
+fn main() {
+    let inputs = [
+        ("day4", "src/bin/day4/input.txt", 'X'),
+        ("day6", "src/bin/day6/input.txt", '#'),
+    ];
+    for (day, file, val) in inputs {
+        let input = std::fs::read_to_string(file).expect("File not found");
+        let field = input.parse::<Field<char>>().expect("Doesn't error");
+        bench(&format!("{day} iter"), &field, |f| {
+            f.iter().filter(|&c| c == &val).count()
+        });
+        bench(&format!("{day} get rows"), &field, |f| scan_rows(f, &val));
+        bench(&format!("{day} get columns"), &field, |f| {
+            scan_columns(f, &val)
+        });
+        bench(&format!("{day} peek neighbours"), &field, |f| {
+            peek_neighbours(f, &val)
+        });
+    }
+
+    let input = std::fs::read_to_string("src/bin/day10/input.txt").expect("File not found");
+    let field = input.parse::<Field<u8>>().expect("Doesn't error");
+    bench("day10 iter", &field, |f| {
+        f.iter().filter(|&&c| c == 0).count()
+    });
+    bench("day10 get rows", &field, |f| scan_rows(f, &0));
+    bench("day10 get columns", &field, |f| scan_columns(f, &0));
+    bench("day10 peek neighbours", &field, |f| peek_neighbours(f, &9));
+}
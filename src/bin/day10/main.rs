@@ -1,13 +1,13 @@
 mod topographical_map;
 mod trailhead;
 
-use std::time::Instant;
+use std::{fs::File, io::BufReader, time::Instant};
 use topographical_map::TopographicalMap;
 use trailhead::TrailHead;
 
 fn main() {
-    let input = std::fs::read_to_string("src/bin/day10/input.txt").unwrap();
-    let map = input.parse::<TopographicalMap>().unwrap();
+    let input = File::open("src/bin/day10/input.txt").unwrap();
+    let map = TopographicalMap::from_reader(BufReader::new(input)).unwrap();
 
     let t = Instant::now();
     let sum = map
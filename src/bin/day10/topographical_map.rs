@@ -1,5 +1,19 @@
-use advent2024::{field::Field, location::Location};
-use std::str::FromStr;
+use advent2024::{
+    field::{Field, FieldError, NotADigit, ReadError, decode_digit},
+    location::{DirVector, Location},
+};
+use std::{io::BufRead, str::FromStr};
+
+// cells marked with '.' can never be stepped onto
+// as no height value can ever be followed by it
+const IMPASSABLE: u8 = u8::MAX;
+
+fn decode_height(c: char) -> Result<u8, NotADigit> {
+    match c {
+        '.' => Ok(IMPASSABLE),
+        c => decode_digit(c),
+    }
+}
 
 #[derive(Debug)]
 pub(crate) struct TopographicalMap(Field<u8>);
@@ -10,19 +24,43 @@ impl TopographicalMap {
         self.0.get(loc)
     }
 
+    #[inline]
+    pub(crate) fn neighbours(
+        &self,
+        loc: Location,
+    ) -> impl Iterator<Item = (Location, DirVector, &u8)> {
+        self.0.neighbours4(loc)
+    }
+
+    pub(crate) fn from_reader(input: impl BufRead) -> Result<Self, ReadError<NotADigit>> {
+        Ok(TopographicalMap(Field::from_reader(input, decode_height)?))
+    }
+
     pub(crate) fn lowests(&self) -> impl Iterator<Item = Location> {
-        self.0
-            .iter()
-            .enumerate()
-            .filter(|&(_, s)| *s == 0)
-            .map(|(idx, _)| self.0.index_to_cartesian(idx))
+        self.0.positions(|&s| s == 0)
     }
 }
 
 impl FromStr for TopographicalMap {
-    type Err = ();
+    type Err = FieldError<NotADigit>;
 
     fn from_str(s: &str) -> Result<Self, Self::Err> {
-        Ok(TopographicalMap(s.parse::<Field<u8>>()?))
+        Ok(TopographicalMap(Field::parse_with(s, decode_height)?))
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn test_parse_impassable() {
+        let map = "..90..9\n...1.98\n...2..7\n6543456\n765.987\n876....\n987...."
+            .parse::<TopographicalMap>()
+            .expect("Failed to parse map");
+        assert_eq!(map.get(Location(0, 0)), Some(&IMPASSABLE));
+        assert_eq!(map.get(Location(3, 0)), Some(&0));
+        assert_eq!(map.lowests().count(), 1);
+        assert!("..9\n.x.".parse::<TopographicalMap>().is_err());
     }
 }
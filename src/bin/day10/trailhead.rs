@@ -29,14 +29,12 @@ impl TrailHead {
             return Some(1);
         }
         Some(
-            [(0, 1), (0, -1), (1, 0), (-1, 0)]
-                .into_iter()
-                .filter_map(|dir| loc.move_relative(dir))
-                .filter_map(|neighbor| {
+            map.neighbours(loc)
+                .filter_map(|(neighbor, _, &nv)| {
                     if self.history.as_ref().is_some_and(|h| h.contains(&neighbor)) {
                         return None;
                     };
-                    if map.get(neighbor).is_some_and(|&nv| nv != val + 1) {
+                    if nv != val + 1 {
                         return None;
                     }
 
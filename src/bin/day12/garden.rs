@@ -1,6 +1,6 @@
 use super::parser;
 use super::plot::Plot;
-use std::collections::{BTreeSet, HashMap};
+use std::collections::HashMap;
 use std::fmt::Debug;
 use std::ops::Index;
 
@@ -34,75 +34,58 @@ impl Index<&usize> for Garden {
 
 impl Debug for Garden {
     fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
-        use itertools::Itertools;
+        use advent2024::field::Field;
+        use advent2024::location::Location;
+        use advent2024::render::{CellStyle, Color, Output, Renderer};
         use std::collections::hash_map::DefaultHasher;
-        use std::fmt::Write as _;
         use std::hash::{Hash, Hasher};
 
-        // Collect all segments from all plots into a BTreeSet.
-        // This flattens the data structure and sorts segments primarily by y-coordinate
-        // and secondarily by segment start, which is crucial for the debug output
-        // to be rendered scanline by scanline and segments within a scanline
-        // in order.
-        let segments = self
-            .plots
-            .iter()
-            .flat_map(|(id, plot)|
-                // For each plot, associate its ID with each of its segments so we can colour it correctly.
-                std::iter::repeat(id).zip(plot.iter()))
-            // Reformat the tuple to prioritize y-coordinate for sorting by BTreeSet.
-            .map(|(p_id, (y, p_seg))| (y, (p_seg, p_id)))
-            // Collect into a BTreeSet to automatically sort the segments.
-            .collect::<BTreeSet<_>>();
-
         // Define a closure to generate a deterministic color based on the plot ID.
         // This ensures that the same plot ID always gets the same color across runs,
         // making the debug output more consistent and easier to follow.
-        let get_color = |p_id: &usize| -> (u8, u8, u8) {
+        let get_color = |p_id: &usize| -> Color {
             let mut hasher = DefaultHasher::new();
             // Hash the plot ID.
             p_id.hash(&mut hasher);
             let hash = hasher.finish();
             // Extract R, G, B components from the hash value.
-            (
-                ((hash >> 16) & 0xFF) as u8, // Red component from bits 16-23
-                ((hash >> 8) & 0xFF) as u8,  // Green component from bits 8-15
-                (hash & 0xFF) as u8,         // Blue component from bits 0-7
-            )
+            Color::TrueColor {
+                r: ((hash >> 16) & 0xFF) as u8, // Red component from bits 16-23
+                g: ((hash >> 8) & 0xFF) as u8,  // Green component from bits 8-15
+                b: (hash & 0xFF) as u8,         // Blue component from bits 0-7
+            }
         };
 
-        // Iterate through the collected segments, grouping them by their y-coordinate (scanline).
-        // `chunk_by` from `itertools` is used to create these groups efficiently.
-        // The output includes ANSI escape codes for background colors to visualize plots.
-        let mut buffer = String::with_capacity(1200);
-        for (y, segs) in segments.into_iter().chunk_by(|&(y, _)| y).into_iter() {
-            // Write the scanline number (y + 1 because y is 0-indexed).
-            // Use {:3} for fixed-width alignment. Handle potential write errors.
-            write!(&mut buffer, "{:3} ", y + 1)?;
-
-            // Iterate through segments belonging to the current scanline.
-            for (_, (p_seg, p_id)) in segs {
-                // Get the deterministic color for the plot ID.
-                let (r, g, b) = get_color(p_id);
-                // Get the plant character for the segment.
-                let plant_char = p_seg.plant();
+        // The garden spans up to the right-most segment end and the last scanline.
+        let (width, height) = self
+            .plots
+            .values()
+            .flat_map(Plot::iter)
+            .fold((0, 0), |(w, h), (y, p_seg)| {
+                (w.max(p_seg.end() as usize), h.max(y + 1))
+            });
 
-                // Write the ANSI escape code to set the background color using 24-bit color (48;2;R;G;B).
-                write!(&mut buffer, "\x1B[48;2;{r};{g};{b}m")?;
-                // Write the plant character repeatedly for the length of the segment.
-                for _ in 0..p_seg.len() {
-                    write!(&mut buffer, "{plant_char}")?;
+        // Paint every segment onto a field, keeping the plant along with its plot's color.
+        let mut field = Field::new(width, height, None);
+        for (p_id, plot) in &self.plots {
+            let color = get_color(p_id);
+            for (y, p_seg) in plot.iter() {
+                for x in p_seg.start()..p_seg.end() {
+                    field.set(Location(x as usize, *y), Some((p_seg.plant(), color)));
                 }
             }
-            // After processing all segments for a scanline,
-            // Write the ANSI escape code to reset text attributes (back to default)
-            // and add a new line
-            writeln!(&mut buffer, "\x1B[0m")?;
         }
-        // write buffer to output
-        write!(f, "{buffer}")?;
 
-        // Return Ok(()) to indicate successful formatting.
+        // Draw each plant over the background color of its plot.
+        let renderer = Renderer::with_style(&field, |cell| match cell {
+            Some((plant, color)) => CellStyle::glyph(plant).bg(*color),
+            None => CellStyle::default(),
+        });
+        for (y, line) in renderer.render(Output::Ansi).lines().enumerate() {
+            // Write the scanline number (y + 1 because y is 0-indexed).
+            // Use {:3} for fixed-width alignment.
+            writeln!(f, "{:3} {line}", y + 1)?;
+        }
         Ok(())
     }
 }
@@ -38,7 +38,7 @@ fn main() {
     let total_2 = calculate_cost(&garden, |(_, plot)| plot.area() * plot.sides_count());
     let el_puzzle_2 = t.elapsed() - el_puzzle_1 - t_parse;
 
-    println!("{:?}", &garden);
+    println!("{garden:?}");
     let el_debug = t.elapsed() - el_puzzle_2 - el_puzzle_1 - t_parse;
 
     println!("Part 1 - Garden total cost : {total_1} = {el_puzzle_1:?}");
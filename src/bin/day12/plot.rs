@@ -154,11 +154,30 @@ impl Plot {
 
 impl Debug for Plot {
     fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
-        use std::fmt::Write as _;
+        use advent2024::field::Field;
+        use advent2024::location::Location;
+        use advent2024::render::{CellStyle, Color, Output, Renderer};
 
-        const SPACE_ANSI: &str = "\x1B[38;2;128;128;128;48;2;16;16;128m";
-        const PLANT_ANSI: &str = "\x1B[38;2;255;255;0;48;2;16;16;128m";
+        const BACKGROUND: Color = Color::TrueColor {
+            r: 16,
+            g: 16,
+            b: 128,
+        };
+        const SPACE: Color = Color::TrueColor {
+            r: 128,
+            g: 128,
+            b: 128,
+        };
+        const PLANT: Color = Color::TrueColor {
+            r: 255,
+            g: 255,
+            b: 0,
+        };
 
+        // capture plot's top & bottom lines; rows are ordered by 'y' first
+        let (Some((top, _)), Some((bottom, _))) = (self.rows.first(), self.rows.last()) else {
+            return Ok(());
+        };
         // capture plot's left & right bounds
         let (left, right) = self
             .rows
@@ -167,48 +186,31 @@ impl Debug for Plot {
                 (left.min(seg.start()), right.max(seg.end()))
             });
 
+        // mark the cells covered by the plot's segments; the rest render as '.'
+        let mut field = Field::new((right - left) as usize, bottom - top + 1, None);
+        for (y, seg) in &self.rows {
+            for x in seg.start()..seg.end() {
+                field.set(Location((x - left) as usize, y - top), Some(seg.plant()));
+            }
+        }
+        let renderer = Renderer::with_style(&field, |cell| {
+            match cell {
+                Some(plant) => CellStyle::glyph(plant).fg(PLANT),
+                None => CellStyle::glyph('.').fg(SPACE),
+            }
+            .bg(BACKGROUND)
+        });
+        let rendered = renderer.render(Output::Ansi);
+        let lines = rendered.lines().collect::<Vec<_>>();
+
         // given all segments are ordered by 'y' and 'seg.start'
         // it is easy and cheap to iterate per line; we chunk by 'y'
         for (y, line_segments) in &self.rows.iter().chunk_by(|(y, _)| *y) {
-            let (next_pos, mut buffer, segs) = line_segments.fold(
-                (
-                    left,
-                    // use a line buffer to render the output
-                    String::with_capacity(20),
-                    // create tmp buffer to store the ranges per line of segments
-                    Vec::with_capacity(20),
-                ),
-                |(mut next_pos, mut buffer, mut segs), (_, seg)| {
-                    // every segment is prefixed with 0..* '.' starting from 'ptr'
-                    write!(&mut buffer, "{SPACE_ANSI}").ok();
-                    for _ in next_pos..seg.start() {
-                        write!(&mut buffer, ".").ok();
-                    }
-                    // write the segment
-                    write!(&mut buffer, "{PLANT_ANSI}").ok();
-                    for _ in seg.start()..seg.end() {
-                        write!(&mut buffer, "{}", seg.plant()).ok();
-                    }
-                    // capture new start position of '.'
-                    next_pos = seg.end();
-                    // save segment for display
-                    segs.push(seg.start()..seg.end());
-
-                    (next_pos, buffer, segs)
-                },
-            );
-
-            // every line finishes with 0..* '.' starting from 'ptr'
-            write!(&mut buffer, "{SPACE_ANSI}")?;
-            for _ in next_pos..right {
-                write!(&mut buffer, ".")?
-            }
-            write!(&mut buffer, "\x1B[0m")?;
-            // write buffer to output
-            write!(f, "{buffer} {y:>2}:")?;
+            write!(f, "{} {y:>2}:", lines[y - top])?;
             // Render the ranges of all the line segments drawn
-            f.debug_list().entries(segs.iter()).finish()?;
-            // write buffer to output
+            f.debug_list()
+                .entries(line_segments.map(|(_, seg)| seg.start()..seg.end()))
+                .finish()?;
             writeln!(f)?;
         }
         Ok(())
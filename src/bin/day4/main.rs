@@ -1,5 +1,6 @@
 use advent2024::field::Field;
 use advent2024::location::{DirVector, Location};
+use advent2024::pattern::Pattern;
 use std::time::Instant;
 
 fn main() {
@@ -24,20 +25,11 @@ fn main() {
     assert_eq!(2603, sum);
 
     let t = Instant::now();
-    let mas_leg1_scanner = search_directions(&field, &[(1, 1)]);
-    let mas_leg2_scanner = search_directions(&field, &[(1, -1)]);
-    let sum = (0..height)
-        .map(|y| {
-            (0..width)
-                .filter(|&x| {
-                    (mas_leg1_scanner("MAS", Location(x, y)).count() == 1
-                        || mas_leg1_scanner("SAM", Location(x, y)).count() == 1)
-                        && (mas_leg2_scanner("MAS", Location(x, y + 2)).count() == 1
-                            || mas_leg2_scanner("SAM", Location(x, y + 2)).count() == 1)
-                })
-                .count()
-        })
-        .sum::<usize>();
+    // the cross can be laid out in any of the four rotations
+    let x_mas = Pattern::parse("M.S\n.A.\nM.S", '.')
+        .expect("Doesn't error")
+        .all_orientations();
+    let sum = field.find_pattern(&x_mas).len();
     println!("Part 2: Found ({sum}) MAS crosses - {:?}", t.elapsed());
     assert_eq!(1965, sum);
 }
@@ -60,19 +52,13 @@ fn search_directions<'a>(
 }
 
 fn is_word_matched(field: &Field<char>, word: &str, start: Location, dir: DirVector) -> bool {
-    word.char_indices().all(|(i, c)| {
-        start
-            // calculate new location based on (a) current index (b) starting position & (c) direction
-            .move_relative((dir.0 * i as isize, dir.1 * i as isize))
-            .map(|p| {
-                field
-                    // match the value in position with input's character
-                    .get(p)
-                    .map(|&val| val == c)
-                    .unwrap_or(false)
-            })
-            .unwrap_or(false)
-    })
+    // walk from the starting position along the direction until the edge of the field,
+    // matching the value of each position with the input's next character
+    field
+        .ray(start, dir)
+        .map(|(_, &val)| val)
+        .take(word.chars().count())
+        .eq(word.chars())
 }
 
 #[test]
@@ -1,15 +1,30 @@
 use advent2024::field::Field;
 use advent2024::location::*;
+#[cfg(test)]
+use advent2024::cycle::DetectCycle;
+#[cfg(test)]
+use advent2024::field::FieldError;
+#[cfg(test)]
+use std::convert::Infallible;
 
 pub type Lab = Field<char>;
 
-#[derive(Debug)]
+#[derive(Debug, Clone)]
 pub(crate) struct Guard<'a> {
     pub lab: &'a Lab,
     pub dir: DirVector,
     pub pos: Location,
 }
 
+impl Guard<'_> {
+    // a guard that steps onto a location already visited from the same direction walks forever.
+    // Generic but slower than tracking visits in a DirectionalGridSet, as main does
+    #[cfg(test)]
+    pub(crate) fn loops(self) -> bool {
+        self.detect_cycle_brent().is_loop()
+    }
+}
+
 impl Iterator for Guard<'_> {
     type Item = (Location, DirVector);
 
@@ -30,25 +45,24 @@ impl Iterator for Guard<'_> {
 }
 
 pub fn find_guard(lab: &Lab, token: &[char]) -> Option<(Location, DirVector)> {
-    lab.iter().position(|c| token.contains(c)).map(|idx| {
-        let loc = lab.index_to_cartesian(idx);
-        (
-            loc,
-            lab.get(loc)
-                .map(|val| match &val {
+    lab.iter_locations()
+        .find(|(_, c)| token.contains(c))
+        .map(|(loc, val)| {
+            (
+                loc,
+                match val {
                     '^' => (0, -1),
                     '>' => (1, 0),
                     'v' => (0, 1),
                     '<' => (-1, 0),
                     _ => unreachable!(),
-                })
-                .unwrap(),
-        )
-    })
+                },
+            )
+        })
 }
 
 #[test]
-fn test_find_guard() -> Result<(), ()> {
+fn test_find_guard() -> Result<(), FieldError<Infallible>> {
     let dt = [
         (
             "...\n.<.\n...\n...",
@@ -70,3 +84,26 @@ fn test_find_guard() -> Result<(), ()> {
     }
     Ok(())
 }
+
+#[test]
+fn test_guard_loops() -> Result<(), FieldError<Infallible>> {
+    let input = std::fs::read_to_string("src/bin/day6/sample.txt").expect("File not found");
+    let mut lab = input.parse::<Lab>()?;
+    let (pos, dir) = find_guard(&lab, &['^']).expect("there is a guard");
+
+    assert!(
+        !Guard {
+            lab: &lab,
+            pos,
+            dir
+        }
+        .loops()
+    );
+    let obstacles = [(3, 6), (6, 7), (7, 7), (1, 8), (3, 8), (7, 9)];
+    for (x, y) in obstacles {
+        let looped =
+            lab.with_temporarily(Location(x, y), '#', |lab| Guard { lab, pos, dir }.loops());
+        assert_eq!(looped, Some(true), "obstacle at ({x},{y})");
+    }
+    Ok(())
+}
@@ -1,7 +1,9 @@
 mod guard;
 
 use crate::guard::*;
+use advent2024::bitset::{DirectionalGridSet, GridSet};
 use advent2024::location::*;
+use advent2024::render::{CellStyle, Output, Renderer};
 use std::{collections::HashMap, time::Instant};
 
 fn main() {
@@ -11,41 +13,36 @@ fn main() {
     let (pos, dir) = find_guard(&lab, &['^', '>', 'v', '<']).expect("there is no Lab Guard !!");
 
     let t = Instant::now();
-    let mut unique_locations = Guard {
+    let mut unique_locations = GridSet::for_field(&lab);
+    Guard {
         lab: &lab,
         pos,
         dir,
     }
-    .collect::<HashMap<_, _>>();
-    unique_locations.insert(pos, dir);
+    .for_each(|(l, _)| {
+        unique_locations.set(l);
+    });
+    unique_locations.set(pos);
     println!(
         "Part 1: Guard visited {:?} unique locations - {:?}",
-        unique_locations.len(),
+        unique_locations.count(),
         t.elapsed()
     );
-    assert_eq!(unique_locations.len(), 5534);
+    assert_eq!(unique_locations.count(), 5534);
 
     let t = Instant::now();
-    let mut path = HashMap::new();
+    let mut path = DirectionalGridSet::for_field(&lab);
     let obstacles = unique_locations
         .iter()
-        .filter(|&(l, _)| {
-            path.clear();
-            *lab.get_mut(*l).unwrap() = '#';
-            // carry on until we either (a) fall off the lab
-            let in_loop = Guard {
-                lab: &lab,
-                pos,
-                dir,
-            }
-            .any(|(nl, nd)| {
-                // or (b) we step onto a position already visited from the same direction
-                let in_loop = path.get(&nl).is_some_and(|&pd| nd == pd);
-                path.entry(nl).or_insert(nd);
-                in_loop
-            });
-            *lab.get_mut(*l).unwrap() = '.';
-            in_loop
+        .filter(|&l| {
+            path.reset();
+            lab.with_temporarily(l, '#', |lab| {
+                // carry on until we either (a) fall off the lab
+                Guard { lab, pos, dir }
+                    // or (b) we step onto a position already visited from the same direction
+                    .any(|(nl, nd)| !path.set(nl, nd))
+            })
+            .unwrap()
         })
         .count();
 
@@ -63,30 +60,19 @@ fn _print_all(
     path: &HashMap<Location, DirVector>,
     obst: Option<&Vec<Location>>,
 ) {
+    let renderer = Renderer::new(guard.lab)
+        .cell_width(2)
+        .glyphs(
+            "path",
+            path.iter().map(|(&l, &d)| (l, dirvector_to_char(d))),
+        )
+        .locations(
+            "obstacle",
+            obst.into_iter().flatten().copied(),
+            CellStyle::glyph('O'),
+        )
+        .locations("guard", [guard.pos], CellStyle::glyph('🚷'))
+        .locations("start", [start], CellStyle::glyph('🚀'));
     println!();
-    (0..guard.lab.height()).for_each(|y| {
-        (0..guard.lab.width()).for_each(|x| {
-            let loc = Location(x, y);
-            if loc == start {
-                print!("🚀");
-                return;
-            };
-            if loc == guard.pos {
-                print!("🚷");
-                return;
-            };
-            let c = match (
-                guard.lab.get(loc),
-                path.get(&loc),
-                obst.map(|o| o.contains(&loc)),
-            ) {
-                (None, _, _) => unreachable!(),
-                (_, _, Some(true)) => 'O',
-                (_, Some(&d), _) => dirvector_to_char(d),
-                (Some(&c), _, _) => c,
-            };
-            print!("{c:2}");
-        });
-        println!();
-    });
+    print!("{}", renderer.render(Output::Ansi));
 }
@@ -1,32 +1,16 @@
 use std::ops::RangeInclusive;
 
 use advent2024::location::Location;
+use advent2024::vector::Vector;
 
 #[derive(Debug, Clone, Copy)]
 pub(crate) struct Antenna(pub Location);
 
 impl Antenna {
     pub fn antinode_pair(&self, rhs: Antenna, harmonics: usize) -> [Option<Location>; 2] {
-        let (dxu, dyu) = self.0.distance(&rhs.0);
-        let (dx, dy) = ((harmonics * dxu) as isize, (harmonics * dyu) as isize);
-        match (self.0.0 >= rhs.0.0, self.0.1 >= rhs.0.1) {
-            (true, true) => [
-                rhs.0.move_relative((-dx, -dy)),
-                self.0.move_relative((dx, dy)),
-            ],
-            (true, false) => [
-                rhs.0.move_relative((-dx, dy)),
-                self.0.move_relative((dx, -dy)),
-            ],
-            (false, true) => [
-                rhs.0.move_relative((dx, -dy)),
-                self.0.move_relative((-dx, dy)),
-            ],
-            (false, false) => [
-                rhs.0.move_relative((dx, dy)),
-                self.0.move_relative((-dx, -dy)),
-            ],
-        }
+        // the antinodes lie beyond either antenna, along the line joining them
+        let offset = Vector::between(rhs.0, self.0) * harmonics as isize;
+        [rhs.0 - offset, self.0 + offset]
     }
 
     pub fn antinodes(
@@ -1,7 +1,10 @@
 use crate::antenna::Antenna;
-use advent2024::{field::Field, location::Location};
+use advent2024::{
+    field::{Field, FieldError},
+    location::Location,
+};
 use itertools::Itertools;
-use std::{collections::HashMap, ops::RangeInclusive, str::FromStr};
+use std::{collections::HashMap, convert::Infallible, ops::RangeInclusive, str::FromStr};
 
 pub(crate) struct City {
     city: Field<char>,
@@ -32,21 +35,16 @@ impl City {
 }
 
 impl FromStr for City {
-    type Err = ();
+    type Err = FieldError<Infallible>;
 
     fn from_str(s: &str) -> Result<Self, Self::Err> {
         let city = s.parse::<Field<char>>()?;
         let antennas: HashMap<char, Vec<Antenna>> = city
-            .iter()
-            .enumerate()
-            .filter(|&(_, c)| c.ne(&'.'))
-            .fold(HashMap::new(), |mut map, (i, &c)| {
-                let loc = city.index_to_cartesian(i);
-                map.entry(c)
-                    .and_modify(|antennas| antennas.push(Antenna(loc)))
-                    .or_insert(vec![Antenna(loc)]);
-                map
-            });
+            .group_by_value()
+            .into_iter()
+            .filter(|&(c, _)| c.ne(&'.'))
+            .map(|(c, locations)| (c, locations.into_iter().map(Antenna).collect()))
+            .collect();
         Ok(City { city, antennas })
     }
 }
@@ -17,7 +17,7 @@ impl DiskMap {
     }
 
     fn insert_file(&mut self, idx: usize, file: Entry) -> &mut Self {
-        if idx % 2 == 0 {
+        if idx.is_multiple_of(2) {
             return self;
         }
         if self.0.get(idx).is_none() {
@@ -33,7 +33,7 @@ impl DiskMap {
     }
 
     fn move_file(&mut self, src: usize, dst: usize) -> &mut Self {
-        if src % 2 != 0 || dst % 2 == 0 {
+        if !src.is_multiple_of(2) || dst.is_multiple_of(2) {
             return self;
         }
         if self.0.get(src).is_none() || self.0.get(dst).is_none() {
@@ -51,7 +51,7 @@ impl DiskMap {
     }
 
     fn remove_file(&mut self, idx: usize) -> &mut Self {
-        if idx % 2 != 0 {
+        if !idx.is_multiple_of(2) {
             return self;
         }
         match (
@@ -142,7 +142,7 @@ impl FromStr for DiskMap {
                 .map(|(idx, num)| {
                     (
                         (num - b'0') as Count,
-                        if idx % 2 == 0 { seq(1) } else { -1 } as Id,
+                        if idx.is_multiple_of(2) { seq(1) } else { -1 } as Id,
                     )
                 })
                 .collect(),
@@ -0,0 +1,268 @@
+use crate::field::Field;
+use crate::location::{DirVector, Direction, Location};
+
+const WORD: usize = u64::BITS as usize;
+
+// fixed size bit array shared by the grid sets below
+#[derive(Debug, Clone, PartialEq, Eq)]
+struct Bits(Vec<u64>);
+
+impl Bits {
+    fn new(len: usize) -> Bits {
+        Bits(vec![0; len.div_ceil(WORD)])
+    }
+    fn test(&self, i: usize) -> bool {
+        self.0[i / WORD] & (1 << (i % WORD)) != 0
+    }
+    // returns whether the bit flipped
+    fn set(&mut self, i: usize) -> bool {
+        let word = &mut self.0[i / WORD];
+        let was_set = *word & (1 << (i % WORD)) != 0;
+        *word |= 1 << (i % WORD);
+        !was_set
+    }
+    fn clear(&mut self, i: usize) -> bool {
+        let word = &mut self.0[i / WORD];
+        let was_set = *word & (1 << (i % WORD)) != 0;
+        *word &= !(1 << (i % WORD));
+        was_set
+    }
+    fn count(&self) -> usize {
+        self.0.iter().map(|w| w.count_ones() as usize).sum()
+    }
+    fn reset(&mut self) {
+        self.0.fill(0);
+    }
+    fn combine(&self, other: &Bits, op: impl Fn(u64, u64) -> u64) -> Bits {
+        Bits(
+            self.0
+                .iter()
+                .zip(&other.0)
+                .map(|(&a, &b)| op(a, b))
+                .collect(),
+        )
+    }
+    // positions of all set bits in ascending order
+    fn ones(&self) -> impl Iterator<Item = usize> + '_ {
+        self.0.iter().enumerate().flat_map(|(w, &word)| {
+            std::iter::successors((word != 0).then_some(word), |&rest| {
+                Some(rest & (rest - 1)).filter(|&r| r != 0)
+            })
+            .map(move |rest| w * WORD + rest.trailing_zeros() as usize)
+        })
+    }
+}
+
+/// Set of locations on a bounded grid, one bit per cell.
+/// Locations outside the grid are never members
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct GridSet {
+    bits: Bits,
+    width: usize,
+    height: usize,
+}
+
+impl GridSet {
+    pub fn new(width: usize, height: usize) -> GridSet {
+        GridSet {
+            bits: Bits::new(width * height),
+            width,
+            height,
+        }
+    }
+    // empty set covering the same area as the field
+    pub fn for_field<T>(field: &Field<T>) -> GridSet {
+        GridSet::new(field.width(), field.height())
+    }
+    fn index(&self, Location(x, y): Location) -> Option<usize> {
+        (x < self.width && y < self.height).then_some(y * self.width + x)
+    }
+    // add a location, returning whether it wasn't already in the set
+    pub fn set(&mut self, l: Location) -> bool {
+        self.index(l).is_some_and(|i| self.bits.set(i))
+    }
+    pub fn test(&self, l: Location) -> bool {
+        self.index(l).is_some_and(|i| self.bits.test(i))
+    }
+    // remove a location, returning whether it was in the set
+    pub fn clear(&mut self, l: Location) -> bool {
+        self.index(l).is_some_and(|i| self.bits.clear(i))
+    }
+    pub fn count(&self) -> usize {
+        self.bits.count()
+    }
+    pub fn is_empty(&self) -> bool {
+        self.bits.0.iter().all(|&w| w == 0)
+    }
+    // empty the set while keeping its allocation
+    pub fn reset(&mut self) {
+        self.bits.reset();
+    }
+    // member locations in row-major order
+    pub fn iter(&self) -> impl Iterator<Item = Location> + '_ {
+        self.bits
+            .ones()
+            .map(|i| Location(i % self.width, i / self.width))
+    }
+
+    fn combine(&self, other: &GridSet, op: impl Fn(u64, u64) -> u64) -> GridSet {
+        assert_eq!(
+            (self.width, self.height),
+            (other.width, other.height),
+            "grid sets must cover the same area"
+        );
+        GridSet {
+            bits: self.bits.combine(&other.bits, op),
+            ..*self
+        }
+    }
+    // panics when the sets cover different areas
+    pub fn union(&self, other: &GridSet) -> GridSet {
+        self.combine(other, |a, b| a | b)
+    }
+    pub fn intersection(&self, other: &GridSet) -> GridSet {
+        self.combine(other, |a, b| a & b)
+    }
+    pub fn difference(&self, other: &GridSet) -> GridSet {
+        self.combine(other, |a, b| a & !b)
+    }
+}
+
+/// Set of (location, heading) pairs on a bounded grid, four bits per cell;
+/// one for each of the four unit directions.
+/// Suits walk histories where revisiting a cell with the same heading means a loop
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct DirectionalGridSet {
+    bits: Bits,
+    width: usize,
+    height: usize,
+}
+
+impl DirectionalGridSet {
+    pub fn new(width: usize, height: usize) -> DirectionalGridSet {
+        DirectionalGridSet {
+            bits: Bits::new(width * height * 4),
+            width,
+            height,
+        }
+    }
+    pub fn for_field<T>(field: &Field<T>) -> DirectionalGridSet {
+        DirectionalGridSet::new(field.width(), field.height())
+    }
+    // first of the four bits of a location
+    fn cell(&self, Location(x, y): Location) -> Option<usize> {
+        (x < self.width && y < self.height).then_some((y * self.width + x) * 4)
+    }
+    // panics if the vector isn't one of the four unit directions
+    fn index(&self, l: Location, d: DirVector) -> Option<usize> {
+        self.cell(l).map(|i| i + Direction::from(d) as usize)
+    }
+    // record a heading at a location, returning whether it wasn't already recorded
+    pub fn set(&mut self, l: Location, d: DirVector) -> bool {
+        self.index(l, d).is_some_and(|i| self.bits.set(i))
+    }
+    pub fn test(&self, l: Location, d: DirVector) -> bool {
+        self.index(l, d).is_some_and(|i| self.bits.test(i))
+    }
+    pub fn clear(&mut self, l: Location, d: DirVector) -> bool {
+        self.index(l, d).is_some_and(|i| self.bits.clear(i))
+    }
+    // whether the location was recorded with any heading
+    pub fn visited(&self, l: Location) -> bool {
+        self.cell(l)
+            .is_some_and(|i| (i..i + 4).any(|i| self.bits.test(i)))
+    }
+    // number of (location, heading) pairs recorded
+    pub fn count(&self) -> usize {
+        self.bits.count()
+    }
+    pub fn reset(&mut self) {
+        self.bits.reset();
+    }
+    // recorded pairs, ordered by location
+    pub fn iter(&self) -> impl Iterator<Item = (Location, DirVector)> + '_ {
+        const HEADINGS: [Direction; 4] = [
+            Direction::Left,
+            Direction::Down,
+            Direction::Right,
+            Direction::Up,
+        ];
+        self.bits.ones().map(|i| {
+            let cell = i / 4;
+            (
+                Location(cell % self.width, cell / self.width),
+                HEADINGS[i % 4].to_cartesian(),
+            )
+        })
+    }
+    // locations recorded with any heading
+    pub fn to_grid_set(&self) -> GridSet {
+        let mut set = GridSet::new(self.width, self.height);
+        self.iter().for_each(|(l, _)| {
+            set.set(l);
+        });
+        set
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn test_grid_set() {
+        let input = std::fs::read_to_string("src/bin/day6/sample.txt").expect("File not found");
+        let lab = input.parse::<Field<char>>().expect("Doesn't error");
+
+        let mut obstacles = GridSet::for_field(&lab);
+        lab.positions(|&c| c == '#').for_each(|l| {
+            assert!(obstacles.set(l));
+        });
+        assert_eq!(obstacles.count(), 8);
+        assert!(!obstacles.set(Location(4, 0)));
+        assert!(obstacles.test(Location(9, 1)));
+        assert!(!obstacles.test(Location(10, 1)));
+        assert!(!obstacles.set(Location(10, 1)));
+        assert_eq!(obstacles.iter().next(), Some(Location(4, 0)));
+        assert_eq!(obstacles.iter().last(), Some(Location(6, 9)));
+
+        let mut column = GridSet::for_field(&lab);
+        (0..10).for_each(|y| {
+            column.set(Location(4, y));
+        });
+        let column = column.union(&obstacles);
+        assert_eq!(column.count(), 17);
+        assert_eq!(
+            obstacles.intersection(&column).iter().collect::<Vec<_>>(),
+            obstacles.iter().collect::<Vec<_>>()
+        );
+        let rest = column.difference(&obstacles);
+        assert_eq!(rest.count(), 9);
+        assert!(!rest.test(Location(4, 0)));
+
+        assert!(obstacles.clear(Location(4, 0)));
+        assert!(!obstacles.clear(Location(4, 0)));
+        obstacles.reset();
+        assert!(obstacles.is_empty());
+    }
+
+    #[test]
+    fn test_directional_set() {
+        let mut path = DirectionalGridSet::new(3, 3);
+        assert!(path.set(Location(1, 1), (0, -1)));
+        assert!(path.set(Location(1, 1), (1, 0)));
+        assert!(!path.set(Location(1, 1), (0, -1)));
+        assert!(path.test(Location(1, 1), (1, 0)));
+        assert!(!path.test(Location(1, 1), (0, 1)));
+        assert!(path.visited(Location(1, 1)));
+        assert!(!path.visited(Location(0, 1)));
+        assert_eq!(path.count(), 2);
+        assert_eq!(
+            path.iter().collect::<Vec<_>>(),
+            [(Location(1, 1), (1, 0)), (Location(1, 1), (0, -1))]
+        );
+        assert_eq!(path.to_grid_set().count(), 1);
+        assert!(path.clear(Location(1, 1), (1, 0)));
+        assert_eq!(path.count(), 1);
+    }
+}
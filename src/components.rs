@@ -0,0 +1,127 @@
+use crate::field::Field;
+use crate::location::{Connectivity, Location};
+
+/// Summary of a connected region of equal values
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Region {
+    pub label: usize,
+    pub cells: usize,
+    // inclusive bounding box corners
+    pub top_left: Location,
+    pub bottom_right: Location,
+    // first cell of the region in row-major order
+    pub representative: Location,
+}
+
+impl Region {
+    fn new(label: usize, loc: Location) -> Region {
+        Region {
+            label,
+            cells: 0,
+            top_left: loc,
+            bottom_right: loc,
+            representative: loc,
+        }
+    }
+
+    fn add(&mut self, Location(x, y): Location) {
+        self.cells += 1;
+        self.top_left = Location(self.top_left.0.min(x), self.top_left.1.min(y));
+        self.bottom_right = Location(self.bottom_right.0.max(x), self.bottom_right.1.max(y));
+    }
+}
+
+impl<T: Eq> Field<T> {
+    // label every connected region of equal values;
+    // label n in the returned map refers to the n-th region summary
+    pub fn components(&self, connectivity: Connectivity) -> (Field<usize>, Vec<Region>) {
+        const UNLABELLED: usize = usize::MAX;
+        let mut labels = Field::new(self.width(), self.height(), UNLABELLED);
+        let mut regions = Vec::new();
+        let mut stack = Vec::new();
+
+        // scan in row-major order; every unlabelled cell seeds a new region
+        for y in 0..self.height() {
+            for x in 0..self.width() {
+                if labels.get(Location(x, y)) != Some(&UNLABELLED) {
+                    continue;
+                }
+                let mut region = Region::new(regions.len(), Location(x, y));
+                *labels.get_mut(Location(x, y)).unwrap() = region.label;
+                stack.push(Location(x, y));
+
+                // flood fill the region
+                while let Some(loc) = stack.pop() {
+                    region.add(loc);
+                    let val = self.get(loc).unwrap();
+                    for (nl, _, nv) in self.neighbours(loc, connectivity.stencil()) {
+                        let label = labels.get_mut(nl).unwrap();
+                        if *label == UNLABELLED && nv == val {
+                            *label = region.label;
+                            stack.push(nl);
+                        }
+                    }
+                }
+                regions.push(region);
+            }
+        }
+        (labels, regions)
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn test_garden_regions() {
+        let input = std::fs::read_to_string("src/bin/day12/sample.txt").expect("File not found");
+        let garden = input.parse::<Field<char>>().expect("Doesn't error");
+
+        let (labels, regions) = garden.components(Connectivity::Four);
+        assert_eq!(regions.len(), 11);
+        assert_eq!(regions.iter().map(|r| r.cells).sum::<usize>(), 100);
+        assert_eq!(
+            regions[0],
+            Region {
+                label: 0,
+                cells: 12,
+                top_left: Location(0, 0),
+                bottom_right: Location(4, 3),
+                representative: Location(0, 0),
+            }
+        );
+        // the two separate 'C' plots
+        let c = regions
+            .iter()
+            .filter(|r| garden.get(r.representative) == Some(&'C'))
+            .map(|r| r.cells)
+            .collect::<Vec<_>>();
+        assert_eq!(c, [14, 1]);
+        assert!(
+            labels
+                .iter()
+                .enumerate()
+                .all(|(i, &l)| garden.get(garden.index_to_cartesian(i))
+                    == garden.get(regions[l].representative))
+        );
+    }
+
+    #[test]
+    fn test_diagonal_regions() {
+        let input = std::fs::read_to_string("src/bin/day12/sample1.txt").expect("File not found");
+        let garden = input.parse::<Field<char>>().expect("Doesn't error");
+
+        assert_eq!(garden.components(Connectivity::Four).1.len(), 5);
+        assert_eq!(garden.components(Connectivity::Eight).1.len(), 5);
+
+        let checkers = "X.X\n.X.\nX.X"
+            .parse::<Field<char>>()
+            .expect("Doesn't error");
+        assert_eq!(checkers.components(Connectivity::Four).1.len(), 9);
+        let (labels, regions) = checkers.components(Connectivity::Eight);
+        assert_eq!(regions.len(), 2);
+        assert_eq!(labels.get(Location(2, 2)), Some(&0));
+        assert_eq!(regions[1].cells, 4);
+    }
+}
@@ -0,0 +1,221 @@
+use crate::field::Field;
+use crate::location::{DirVector, Location, NEIGHBOURS4, turn_ccw, turn_cw};
+use std::collections::HashMap;
+
+/// Closed boundary line around a region, running along cell edges.
+///
+/// Vertices are lattice points where the line turns; point (x, y) is the top-left corner
+/// of cell (x, y), so a field of w x h cells spans points (0, 0) to (w, h).
+/// Outer boundaries run clockwise and holes anti-clockwise, with y growing downwards,
+/// keeping the region on the right hand side of the line
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Contour {
+    // starting at the top-most, left-most vertex
+    pub vertices: Vec<Location>,
+    pub hole: bool,
+}
+
+impl Contour {
+    // number of cell edges along the line
+    pub fn perimeter(&self) -> usize {
+        self.segments()
+            .map(|(a, b)| {
+                let (dx, dy) = a.distance(&b);
+                dx + dy
+            })
+            .sum()
+    }
+    // number of straight runs, as many as there are vertices
+    pub fn sides(&self) -> usize {
+        self.vertices.len()
+    }
+    // consecutive vertex pairs, closing the line back to the first vertex
+    pub fn segments(&self) -> impl Iterator<Item = (Location, Location)> + '_ {
+        self.vertices
+            .iter()
+            .copied()
+            .zip(self.vertices.iter().copied().cycle().skip(1))
+    }
+}
+
+impl<T> Field<T> {
+    // outlines of all cells matching the predicate, outer boundaries and holes alike.
+    // Cells touching only at a corner are kept apart, as with Connectivity::Four
+    pub fn contours(&self, predicate: impl Fn(&T) -> bool) -> Vec<Contour> {
+        let inside = |l: Option<Location>| l.and_then(|l| self.get(l)).is_some_and(&predicate);
+
+        // every cell side facing outside the region, walked clockwise around its cell
+        // i.e. the top side runs from the cell's top-left to its top-right corner
+        let mut edges = Vec::new();
+        for l in self.positions(&predicate) {
+            let Location(x, y) = l;
+            let corners = [
+                Location(x, y),
+                Location(x + 1, y),
+                Location(x + 1, y + 1),
+                Location(x, y + 1),
+            ];
+            // up, right, down, left sides start at the corners in the same order
+            for (side, &d) in NEIGHBOURS4.iter().enumerate() {
+                if !inside(l.move_relative(d)) {
+                    edges.push((corners[side], turn_cw(d)));
+                }
+            }
+        }
+        let mut outgoing: HashMap<Location, Vec<usize>> = HashMap::new();
+        for (i, &(start, _)) in edges.iter().enumerate() {
+            outgoing.entry(start).or_default().push(i);
+        }
+
+        let mut used = vec![false; edges.len()];
+        let mut contours = Vec::new();
+        for first in 0..edges.len() {
+            if used[first] {
+                continue;
+            }
+            let mut path: Vec<(Location, DirVector)> = Vec::new();
+            let mut e = first;
+            loop {
+                used[e] = true;
+                let (start, d) = edges[e];
+                path.push((start, d));
+                let end = start.move_relative(d).expect("edges stay on the lattice");
+                // where two cells touch at a corner the vertex has two ways out;
+                // turning right first hugs the cell just walked along
+                let next = [turn_cw(d), d, turn_ccw(d)]
+                    .into_iter()
+                    .find_map(|nd| outgoing[&end].iter().copied().find(|&n| edges[n].1 == nd))
+                    .expect("boundaries are closed");
+                if next == first {
+                    break;
+                }
+                e = next;
+            }
+            contours.push(Contour::from_path(&path));
+        }
+        contours
+    }
+}
+
+impl Contour {
+    // keep the turning points of a closed path of unit steps
+    fn from_path(path: &[(Location, DirVector)]) -> Contour {
+        let mut vertices = path
+            .iter()
+            .zip(path.iter().cycle().skip(path.len() - 1))
+            .filter(|((_, d), (_, prev))| d != prev)
+            .map(|((l, _), _)| *l)
+            .collect::<Vec<_>>();
+        let top_left = (0..vertices.len())
+            .min_by_key(|&i| (vertices[i].1, vertices[i].0))
+            .unwrap_or(0);
+        vertices.rotate_left(top_left);
+
+        // shoelace formula; twice the enclosed area, negative when anti-clockwise
+        let area = path
+            .iter()
+            .zip(path.iter().cycle().skip(1))
+            .map(|((a, _), (b, _))| (a.0 * b.1) as isize - (b.0 * a.1) as isize)
+            .sum::<isize>();
+        Contour {
+            vertices,
+            hole: area < 0,
+        }
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::location::Connectivity;
+
+    #[test]
+    fn test_shapes() {
+        let field = "....\n.##.\n.#..\n...."
+            .parse::<Field<char>>()
+            .expect("Doesn't error");
+        let contours = field.contours(|&c| c == '#');
+        assert_eq!(
+            contours,
+            [Contour {
+                vertices: vec![
+                    Location(1, 1),
+                    Location(3, 1),
+                    Location(3, 2),
+                    Location(2, 2),
+                    Location(2, 3),
+                    Location(1, 3)
+                ],
+                hole: false
+            }]
+        );
+        assert_eq!((contours[0].perimeter(), contours[0].sides()), (8, 6));
+
+        // a ring has an outer boundary and a hole, the field edge bounds the outline
+        let ring = "###\n#.#\n###"
+            .parse::<Field<char>>()
+            .expect("Doesn't error");
+        let contours = ring.contours(|&c| c == '#');
+        assert_eq!(contours.len(), 2);
+        assert!(!contours[0].hole && contours[1].hole);
+        assert_eq!(
+            contours[0].vertices,
+            [
+                Location(0, 0),
+                Location(3, 0),
+                Location(3, 3),
+                Location(0, 3)
+            ]
+        );
+        assert_eq!(
+            contours[1].vertices,
+            [
+                Location(1, 1),
+                Location(1, 2),
+                Location(2, 2),
+                Location(2, 1)
+            ]
+        );
+        assert_eq!(contours.iter().map(Contour::perimeter).sum::<usize>(), 16);
+
+        // cells touching at a corner have separate outlines
+        let checkers = "#.\n.#".parse::<Field<char>>().expect("Doesn't error");
+        let contours = checkers.contours(|&c| c == '#');
+        assert_eq!(contours.len(), 2);
+        assert!(contours.iter().all(|c| c.sides() == 4 && !c.hole));
+        assert!(checkers.contours(|&c| c == 'x').is_empty());
+    }
+
+    #[test]
+    fn test_garden_fences() {
+        for (sample, price, discount) in [
+            ("src/bin/day12/sample.txt", 1930, 1206),
+            ("src/bin/day12/sample1.txt", 772, 436),
+            ("src/bin/day12/input.txt", 1533024, 910066),
+        ] {
+            let input = std::fs::read_to_string(sample).expect("File not found");
+            let garden = input.parse::<Field<char>>().expect("Doesn't error");
+            let (labels, regions) = garden.components(Connectivity::Four);
+
+            let (mut total, mut total_sides) = (0, 0);
+            for r in &regions {
+                let contours = labels.contours(|&l| l == r.label);
+                assert_eq!(contours.iter().filter(|c| !c.hole).count(), 1);
+                total += r.cells * contours.iter().map(Contour::perimeter).sum::<usize>();
+                total_sides += r.cells * contours.iter().map(Contour::sides).sum::<usize>();
+            }
+            assert_eq!((total, total_sides), (price, discount), "{sample}");
+        }
+
+        // the outer plot touches itself diagonally between the two inner ones,
+        // which meet at that corner and so make up a single hole
+        let garden = "AAAAAA\nAAABBA\nAAABBA\nABBAAA\nABBAAA\nAAAAAA"
+            .parse::<Field<char>>()
+            .expect("Doesn't error");
+        let contours = garden.contours(|&c| c == 'A');
+        assert_eq!(contours.len(), 2);
+        assert!(contours[1].hole);
+        assert_eq!((contours[0].sides(), contours[1].sides()), (4, 8));
+        assert_eq!(contours[1].perimeter(), 16);
+    }
+}
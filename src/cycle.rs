@@ -0,0 +1,167 @@
+use std::collections::HashMap;
+use std::hash::Hash;
+
+/// How a run of states played out, where a state is an item yielded by the iterator
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Outcome {
+    // the iterator ran out after this many items
+    Terminated { length: usize },
+    // items from index `prefix` onwards repeat every `cycle` items
+    Looped { prefix: usize, cycle: usize },
+}
+
+impl Outcome {
+    pub fn is_loop(&self) -> bool {
+        matches!(self, Outcome::Looped { .. })
+    }
+}
+
+/// Cycle detection over iterators whose next item depends only on the current one,
+/// i.e. simulations yielding their whole state at every step.
+///
+/// A step function can be turned into such an iterator with `std::iter::successors`
+pub trait DetectCycle: Iterator + Sized {
+    // remembers every item seen; one pass but memory grows with the run
+    fn detect_cycle(self) -> Outcome
+    where
+        Self::Item: Hash + Eq,
+    {
+        let mut seen = HashMap::new();
+        for (i, item) in self.enumerate() {
+            if let Some(&first) = seen.get(&item) {
+                return Outcome::Looped {
+                    prefix: first,
+                    cycle: i - first,
+                };
+            }
+            seen.insert(item, i);
+        }
+        Outcome::Terminated { length: seen.len() }
+    }
+
+    // tortoise and hare; constant memory, replays the run from cloned iterators
+    fn detect_cycle_floyd(self) -> Outcome
+    where
+        Self: Clone,
+        Self::Item: PartialEq,
+    {
+        let (mut tortoise, mut hare) = (self.clone(), self.clone());
+        let mut hare_steps = 0;
+        // the hare moves twice as fast; they meet once both are in the cycle,
+        // a multiple of the cycle length apart
+        let gap = loop {
+            let Some(t) = tortoise.next() else {
+                // only for an empty run, the hare runs out first otherwise
+                return Outcome::Terminated { length: 0 };
+            };
+            hare_steps += 1;
+            if hare.next().is_none() {
+                return Outcome::Terminated {
+                    length: hare_steps - 1,
+                };
+            }
+            hare_steps += 1;
+            match hare.next() {
+                None => {
+                    return Outcome::Terminated {
+                        length: hare_steps - 1,
+                    };
+                }
+                Some(h) if h == t => break hare_steps / 2,
+                Some(_) => (),
+            }
+        };
+        find_cycle(self, gap)
+    }
+
+    // power-of-two search for the cycle length; constant memory and fewer steps than Floyd
+    fn detect_cycle_brent(self) -> Outcome
+    where
+        Self: Clone,
+        Self::Item: PartialEq,
+    {
+        let mut hare = self.clone();
+        let Some(mut tortoise) = hare.next() else {
+            return Outcome::Terminated { length: 0 };
+        };
+        let (mut steps, mut power, mut cycle) = (1, 1, 0);
+        loop {
+            let Some(h) = hare.next() else {
+                return Outcome::Terminated { length: steps };
+            };
+            steps += 1;
+            cycle += 1;
+            if h == tortoise {
+                break;
+            }
+            if power == cycle {
+                tortoise = h;
+                power *= 2;
+                cycle = 0;
+            }
+        }
+        find_cycle(self, cycle)
+    }
+}
+
+impl<I: Iterator> DetectCycle for I {}
+
+// given a gap that is a multiple of the cycle length, the prefix ends at the first
+// item equal to the one a gap further on. The cycle length is then the distance
+// to the next repeat of that item
+fn find_cycle<I>(iter: I, gap: usize) -> Outcome
+where
+    I: Iterator + Clone,
+    I::Item: PartialEq,
+{
+    let mut ahead = iter.clone().skip(gap);
+    let mut behind = iter.enumerate();
+    let (prefix, first) = loop {
+        match (behind.next(), ahead.next()) {
+            (Some((i, b)), Some(a)) if a == b => break (i, b),
+            (Some(_), Some(_)) => (),
+            _ => unreachable!("a looping run never ends"),
+        }
+    };
+    let cycle = behind
+        .position(|(_, b)| b == first)
+        .expect("a looping run repeats")
+        + 1;
+    Outcome::Looped { prefix, cycle }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn test_strategies() {
+        // x -> x * x + 1 mod 255 starting at 3 visits 3, 10, 101, 2, 5, 26, 167, 95, 101, ..
+        let run = std::iter::successors(Some(3_u32), |&x| Some((x * x + 1) % 255));
+        let looped = Outcome::Looped {
+            prefix: 2,
+            cycle: 6,
+        };
+        assert_eq!(run.clone().detect_cycle(), looped);
+        assert_eq!(run.clone().detect_cycle_floyd(), looped);
+        assert_eq!(run.detect_cycle_brent(), looped);
+
+        for n in 0..5 {
+            let run = 0..n;
+            let done = Outcome::Terminated { length: n };
+            assert_eq!(run.clone().detect_cycle(), done);
+            assert_eq!(run.clone().detect_cycle_floyd(), done);
+            assert_eq!(run.detect_cycle_brent(), done);
+        }
+
+        // a fixed point is a cycle of one, prefix lengths around powers of two
+        for prefix in 0..20 {
+            let run = (0..prefix).chain(std::iter::repeat(100));
+            let looped = Outcome::Looped { prefix, cycle: 1 };
+            assert_eq!(run.clone().detect_cycle(), looped);
+            assert_eq!(run.clone().detect_cycle_floyd(), looped);
+            assert_eq!(run.detect_cycle_brent(), looped);
+            assert!(looped.is_loop());
+        }
+    }
+}
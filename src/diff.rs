@@ -0,0 +1,107 @@
+use crate::field::Field;
+use crate::location::Location;
+
+impl<T: PartialEq> Field<T> {
+    // cells holding different values in the two fields, in row-major order
+    // panics when the fields cover different areas
+    pub fn diff<'a>(&'a self, other: &'a Field<T>) -> Vec<(Location, &'a T, &'a T)> {
+        assert_eq!(
+            (self.width(), self.height()),
+            (other.width(), other.height()),
+            "fields must cover the same area"
+        );
+        self.iter_locations()
+            .zip(other.iter())
+            .filter(|((_, a), b)| a != b)
+            .map(|((l, a), b)| (l, a, b))
+            .collect()
+    }
+}
+
+/// The cells that changed between two snapshots of a field, with both values kept,
+/// so that the change can be replayed onto the earlier snapshot or undone on the later one
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct FieldPatch<T> {
+    // location, value before, value after
+    changes: Vec<(Location, T, T)>,
+}
+
+impl<T: Clone + PartialEq> FieldPatch<T> {
+    // panics when the fields cover different areas
+    pub fn new(before: &Field<T>, after: &Field<T>) -> FieldPatch<T> {
+        FieldPatch {
+            changes: before
+                .diff(after)
+                .into_iter()
+                .map(|(l, a, b)| (l, a.clone(), b.clone()))
+                .collect(),
+        }
+    }
+    // turn the earlier snapshot into the later one
+    pub fn apply(&self, field: &mut Field<T>) {
+        self.changes.iter().for_each(|(l, _, after)| {
+            field[*l] = after.clone();
+        });
+    }
+    // turn the later snapshot back into the earlier one
+    pub fn revert(&self, field: &mut Field<T>) {
+        self.changes.iter().for_each(|(l, before, _)| {
+            field[*l] = before.clone();
+        });
+    }
+}
+
+impl<T> FieldPatch<T> {
+    // changed cells in row-major order, with their values before and after
+    pub fn iter(&self) -> impl Iterator<Item = (Location, &T, &T)> {
+        self.changes.iter().map(|(l, a, b)| (*l, a, b))
+    }
+    pub fn len(&self) -> usize {
+        self.changes.len()
+    }
+    pub fn is_empty(&self) -> bool {
+        self.changes.is_empty()
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn test_diff_and_patch() {
+        let input = std::fs::read_to_string("src/bin/day6/sample.txt").expect("File not found");
+        let before = input.parse::<Field<char>>().expect("Doesn't error");
+        let mut after = before.clone();
+        after[Location(3, 6)] = '#';
+        after[Location(4, 6)] = '.';
+        after[Location(4, 5)] = '^';
+
+        assert!(before.diff(&before).is_empty());
+        assert_eq!(
+            before.diff(&after),
+            [
+                (Location(4, 5), &'.', &'^'),
+                (Location(3, 6), &'.', &'#'),
+                (Location(4, 6), &'^', &'.')
+            ]
+        );
+
+        let patch = FieldPatch::new(&before, &after);
+        assert_eq!(patch.len(), 3);
+        assert_eq!(patch.iter().next(), Some((Location(4, 5), &'.', &'^')));
+
+        let mut lab = before.clone();
+        patch.apply(&mut lab);
+        assert!(lab.diff(&after).is_empty());
+        patch.revert(&mut lab);
+        assert!(lab.diff(&before).is_empty());
+        assert!(FieldPatch::new(&lab, &before).is_empty());
+    }
+
+    #[test]
+    #[should_panic(expected = "same area")]
+    fn test_diff_size() {
+        Field::new(2, 2, 0).diff(&Field::new(2, 3, 0));
+    }
+}
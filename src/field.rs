@@ -1,23 +1,89 @@
-use crate::location::DirVector;
+use crate::location::{DirVector, NEIGHBOURS4, NEIGHBOURS8};
 use std::{
+    collections::HashMap,
+    convert::Infallible,
     fmt::{Debug, Display},
+    hash::Hash,
+    io::{self, BufRead},
+    ops::{Index, IndexMut},
     str::FromStr,
 };
+use thiserror::Error;
 
 use super::location::Location;
 
+// cells are stored row after row in a single contiguous vector
+// hence a location (x, y) lives at index y * width + x.
+// Height is kept alongside as it can't be told from the cells when the width is zero
+#[derive(Clone)]
 pub struct Field<T> {
-    cells: Vec<Vec<T>>,
+    cells: Vec<T>,
+    width: usize,
+    height: usize,
+}
+
+/// Errors raised while building a `Field` out of text input.
+/// Line and column are 1-based so they match what an editor shows
+#[derive(Debug, Clone, PartialEq, Eq, Error)]
+pub enum FieldError<E> {
+    #[error("line {line}, column {column}: cannot decode cell {cell:?}: {error}")]
+    InvalidCell {
+        line: usize,
+        column: usize,
+        cell: char,
+        error: E,
+    },
+    #[error("line {line}: expected {expected} cells but found {found}")]
+    RaggedRow {
+        line: usize,
+        expected: usize,
+        found: usize,
+    },
+}
+
+/// Errors raised while reading a `Field` from a stream
+#[derive(Debug, Error)]
+pub enum ReadError<E> {
+    #[error("line {line}: {source}")]
+    Io { line: usize, source: io::Error },
+    #[error(transparent)]
+    Field(#[from] FieldError<E>),
+}
+
+/// Converts a single input character into a cell value.
+/// Any `Fn(char) -> Result<T, E>` closure is a decoder
+pub trait CellDecoder<T> {
+    type Error;
+    fn decode(&self, c: char) -> Result<T, Self::Error>;
+}
+
+impl<T, E, F> CellDecoder<T> for F
+where
+    F: Fn(char) -> Result<T, E>,
+{
+    type Error = E;
+
+    fn decode(&self, c: char) -> Result<T, E> {
+        self(c)
+    }
+}
+
+/// Decoder error for cells expected to hold a single decimal digit
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Error)]
+#[error("not a decimal digit")]
+pub struct NotADigit;
+
+pub fn decode_digit(c: char) -> Result<u8, NotADigit> {
+    c.to_digit(10).map(|d| d as u8).ok_or(NotADigit)
 }
 
 impl<T> Field<T> {
-    pub fn get(&self, Location(x, y): Location) -> Option<&T> {
-        // use build-in bounds checker of the two arrays
+    pub fn get(&self, l: Location) -> Option<&T> {
         // out of bounds will result to None
-        self.cells.get(y).and_then(|w| w.get(x))
+        self.cartesian_to_index(l).map(|idx| &self.cells[idx])
     }
-    pub fn get_mut(&mut self, Location(x, y): Location) -> Option<&mut T> {
-        self.cells.get_mut(y).and_then(|w| w.get_mut(x))
+    pub fn get_mut(&mut self, l: Location) -> Option<&mut T> {
+        self.cartesian_to_index(l).map(|idx| &mut self.cells[idx])
     }
     pub fn peek(&self, l: Location, d: DirVector) -> Option<&T> {
         l.move_relative(d).and_then(|l| self.get(l))
@@ -25,54 +91,441 @@ impl<T> Field<T> {
     pub fn peek_mut(&mut self, l: Location, d: DirVector) -> Option<&mut T> {
         l.move_relative(d).and_then(|l| self.get_mut(l))
     }
+    // cells met walking from start (included) along the vector until falling off the field
+    pub fn ray(&self, start: Location, dir: DirVector) -> impl Iterator<Item = (Location, &T)> {
+        start
+            .ray(dir)
+            .map_while(|l| self.get(l).map(|val| (l, val)))
+    }
+    // first cell past the start along the vector matching the predicate, i.e. line of sight
+    pub fn first_hit(
+        &self,
+        start: Location,
+        dir: DirVector,
+        predicate: impl Fn(&T) -> bool,
+    ) -> Option<(Location, &T)> {
+        self.ray(start, dir).skip(1).find(|(_, val)| predicate(val))
+    }
+    // toroidal addressing; stepping off one edge re-enters from the opposite one
+    // None only when the field has no cells
+    pub fn wrap(&self, l: Location, d: DirVector) -> Option<Location> {
+        (!self.cells.is_empty()).then(|| l.move_wrapping(d, (self.width(), self.height())))
+    }
+    pub fn peek_wrapping(&self, l: Location, d: DirVector) -> Option<&T> {
+        self.wrap(l, d).and_then(|l| self.get(l))
+    }
+    pub fn peek_wrapping_mut(&mut self, l: Location, d: DirVector) -> Option<&mut T> {
+        self.wrap(l, d).and_then(|l| self.get_mut(l))
+    }
+    // cells around a location with edges wrapping around;
+    // on narrow fields different offsets may land on the same cell
+    pub fn neighbours_wrapping<'a>(
+        &'a self,
+        l: Location,
+        stencil: &'a [DirVector],
+    ) -> impl Iterator<Item = (Location, DirVector, &'a T)> {
+        stencil.iter().filter_map(move |&d| {
+            self.wrap(l, d)
+                .and_then(|nl| self.get(nl).map(|val| (nl, d, val)))
+        })
+    }
+    // in-bounds cells around a location, as found by applying each stencil offset in turn
+    pub fn neighbours<'a>(
+        &'a self,
+        l: Location,
+        stencil: &'a [DirVector],
+    ) -> impl Iterator<Item = (Location, DirVector, &'a T)> {
+        stencil.iter().filter_map(move |&d| {
+            l.move_relative(d)
+                .and_then(|nl| self.get(nl).map(|val| (nl, d, val)))
+        })
+    }
+    pub fn neighbours4(&self, l: Location) -> impl Iterator<Item = (Location, DirVector, &T)> {
+        self.neighbours(l, &NEIGHBOURS4)
+    }
+    pub fn neighbours8(&self, l: Location) -> impl Iterator<Item = (Location, DirVector, &T)> {
+        self.neighbours(l, &NEIGHBOURS8)
+    }
+    // same as `neighbours` but with mutable access to the cells;
+    // stencil offsets are expected to be distinct, duplicates are yielded once
+    pub fn neighbours_mut(
+        &mut self,
+        l: Location,
+        stencil: &[DirVector],
+    ) -> impl Iterator<Item = (Location, DirVector, &mut T)> {
+        let mut targets = stencil
+            .iter()
+            .enumerate()
+            .filter_map(|(order, &d)| {
+                l.move_relative(d)
+                    .and_then(|nl| self.cartesian_to_index(nl).map(|idx| (idx, order, nl, d)))
+            })
+            .collect::<Vec<_>>();
+        targets.sort_unstable_by_key(|&(idx, ..)| idx);
+        targets.dedup_by_key(|&mut (idx, ..)| idx);
+
+        // walk the cells once in index order handing out disjoint mutable references
+        let mut cells = self.cells.iter_mut();
+        let mut last = 0;
+        let mut found = targets
+            .into_iter()
+            .filter_map(|(idx, order, nl, d)| {
+                let cell = cells.nth(idx - last)?;
+                last = idx + 1;
+                Some((order, (nl, d, cell)))
+            })
+            .collect::<Vec<_>>();
+        // restore stencil order
+        found.sort_unstable_by_key(|&(order, _)| order);
+        found.into_iter().map(|(_, n)| n)
+    }
+    pub fn neighbours4_mut(
+        &mut self,
+        l: Location,
+    ) -> impl Iterator<Item = (Location, DirVector, &mut T)> {
+        self.neighbours_mut(l, &NEIGHBOURS4)
+    }
+    pub fn neighbours8_mut(
+        &mut self,
+        l: Location,
+    ) -> impl Iterator<Item = (Location, DirVector, &mut T)> {
+        self.neighbours_mut(l, &NEIGHBOURS8)
+    }
     pub fn within_bounds(&self, l: Location) -> bool {
         l.0 < self.width() && l.1 < self.height()
     }
     pub fn iter(&self) -> impl Iterator<Item = &T> {
-        self.cells.iter().flat_map(|c| c.iter())
+        self.cells.iter()
+    }
+    pub fn iter_mut(&mut self) -> impl Iterator<Item = &mut T> {
+        self.cells.iter_mut()
+    }
+    // every cell along with its location, in row-major order
+    pub fn iter_locations(&self) -> impl Iterator<Item = (Location, &T)> {
+        let width = self.width;
+        self.cells
+            .iter()
+            .enumerate()
+            .map(move |(idx, val)| (Location(idx % width, idx / width), val))
+    }
+    pub fn iter_locations_mut(&mut self) -> impl Iterator<Item = (Location, &mut T)> {
+        let width = self.width;
+        self.cells
+            .iter_mut()
+            .enumerate()
+            .map(move |(idx, val)| (Location(idx % width, idx / width), val))
+    }
+    // location of the first cell in row-major order matching the predicate
+    pub fn find(&self, predicate: impl Fn(&T) -> bool) -> Option<Location> {
+        self.positions(predicate).next()
+    }
+    // locations of all cells matching the predicate, in row-major order
+    pub fn positions(&self, predicate: impl Fn(&T) -> bool) -> impl Iterator<Item = Location> {
+        self.iter_locations()
+            .filter(move |(_, val)| predicate(val))
+            .map(|(l, _)| l)
+    }
+    // locations of every distinct value, each list in row-major order
+    pub fn group_by_value(&self) -> HashMap<T, Vec<Location>>
+    where
+        T: Hash + Eq + Clone,
+    {
+        self.iter_locations()
+            .fold(HashMap::new(), |mut map, (l, val)| {
+                map.entry(val.clone()).or_insert_with(Vec::new).push(l);
+                map
+            })
+    }
+    // number of cells holding each distinct value
+    pub fn histogram(&self) -> HashMap<T, usize>
+    where
+        T: Hash + Eq + Clone,
+    {
+        self.cells.iter().fold(HashMap::new(), |mut map, val| {
+            *map.entry(val.clone()).or_insert(0) += 1;
+            map
+        })
+    }
+    // all cells in row-major order
+    pub fn as_slice(&self) -> &[T] {
+        &self.cells
+    }
+    pub fn as_mut_slice(&mut self) -> &mut [T] {
+        &mut self.cells
+    }
+    // row y as a contiguous slice
+    pub fn row(&self, y: usize) -> Option<&[T]> {
+        (y < self.height()).then(|| &self.cells[y * self.width..(y + 1) * self.width])
+    }
+    pub fn row_mut(&mut self, y: usize) -> Option<&mut [T]> {
+        (y < self.height()).then(|| &mut self.cells[y * self.width..(y + 1) * self.width])
+    }
+    // one slice per row, empty ones when the field has no columns
+    pub fn rows(&self) -> impl Iterator<Item = &[T]> {
+        let width = self.width;
+        (0..self.height).map(move |y| &self.cells[y * width..(y + 1) * width])
+    }
+    pub fn rows_mut(&mut self) -> impl Iterator<Item = &mut [T]> {
+        let (width, height) = (self.width, self.height);
+        let mut rest = self.cells.as_mut_slice();
+        (0..height).map(move |_| {
+            let (row, tail) = std::mem::take(&mut rest).split_at_mut(width);
+            rest = tail;
+            row
+        })
+    }
+    // column x walks the cells with a stride of one row;
+    // out of bounds results to an empty iterator
+    pub fn column(&self, x: usize) -> impl Iterator<Item = &T> {
+        let height = if x < self.width { self.height() } else { 0 };
+        self.cells
+            .iter()
+            .skip(x)
+            .step_by(self.width.max(1))
+            .take(height)
+    }
+    pub fn column_mut(&mut self, x: usize) -> impl Iterator<Item = &mut T> {
+        let height = if x < self.width { self.height() } else { 0 };
+        let stride = self.width.max(1);
+        self.cells.iter_mut().skip(x).step_by(stride).take(height)
     }
     pub fn width(&self) -> usize {
-        self.cells.first().map(|v| v.len()).unwrap_or(0)
+        self.width
     }
     pub fn height(&self) -> usize {
-        self.cells.len()
+        self.height
     }
     pub fn index_to_cartesian(&self, idx: usize) -> Location {
         Location(idx % self.width(), idx / self.width())
     }
+    fn cartesian_to_index(&self, Location(x, y): Location) -> Option<usize> {
+        (x < self.width && y < self.height()).then(|| y * self.width + x)
+    }
     pub fn new(w: usize, h: usize, d: T) -> Field<T>
     where
         T: Clone,
     {
         Field {
-            cells: vec![vec![d; w]; h],
+            cells: vec![d; w * h],
+            width: w,
+            height: h,
+        }
+    }
+    // cells are expected in row-major order, width * height of them
+    pub(crate) fn from_cells(cells: Vec<T>, width: usize, height: usize) -> Field<T> {
+        debug_assert_eq!(cells.len(), width * height);
+        Field {
+            cells,
+            width,
+            height,
+        }
+    }
+    // build a field by computing every cell from its location, in row-major order
+    pub fn from_fn(w: usize, h: usize, mut f: impl FnMut(Location) -> T) -> Field<T> {
+        Field {
+            cells: (0..h)
+                .flat_map(|y| (0..w).map(move |x| Location(x, y)))
+                .map(&mut f)
+                .collect(),
+            width: w,
+            height: h,
         }
     }
 }
 
-impl FromStr for Field<char> {
-    type Err = ();
+impl<T> Field<T> {
+    // build a field out of text, one row per line, one cell per character
+    // every row must have the same number of cells as the first one.
+    // The grid ends at the first blank line; anything after it is ignored
+    pub fn parse_with<D>(s: &str, decoder: D) -> Result<Field<T>, FieldError<D::Error>>
+    where
+        D: CellDecoder<T>,
+    {
+        let mut width = None;
+        let mut cells = Vec::with_capacity(s.len());
+        let mut height = 0;
+        for line in s.lines().take_while(|l| !l.is_empty()) {
+            height += 1;
+            Self::decode_row(&mut cells, &mut width, height, line, &decoder)?;
+        }
+        Ok(Field {
+            cells,
+            width: width.unwrap_or(0),
+            height,
+        })
+    }
 
-    fn from_str(s: &str) -> Result<Self, Self::Err> {
+    // same as parse_with() but reading one line at a time, i.e. from a file or stdin,
+    // so it also stops after the first blank line. Passing `&mut reader` leaves whatever
+    // follows the grid in the stream, so further sections can be read from it
+    pub fn from_reader<D>(
+        mut input: impl BufRead,
+        decoder: D,
+    ) -> Result<Field<T>, ReadError<D::Error>>
+    where
+        D: CellDecoder<T>,
+    {
+        let mut width = None;
+        let mut cells = Vec::new();
+        let mut height = 0;
+        let mut buf = String::new();
+        for y in 1.. {
+            buf.clear();
+            match input.read_line(&mut buf) {
+                Ok(0) => break,
+                Ok(_) => (),
+                Err(source) => return Err(ReadError::Io { line: y, source }),
+            }
+            let line = buf.trim_end_matches(['\n', '\r']);
+            if line.is_empty() {
+                break;
+            }
+            Self::decode_row(&mut cells, &mut width, y, line, &decoder)?;
+            height = y;
+        }
         Ok(Field {
-            cells: s
-                .lines()
-                .map(|s| s.chars().collect::<Vec<char>>())
-                .collect::<Vec<_>>(),
+            cells,
+            width: width.unwrap_or(0),
+            height,
         })
     }
+
+    // append a row of cells; its width must match the first row's
+    fn decode_row<D>(
+        cells: &mut Vec<T>,
+        width: &mut Option<usize>,
+        line_no: usize,
+        line: &str,
+        decoder: &D,
+    ) -> Result<(), FieldError<D::Error>>
+    where
+        D: CellDecoder<T>,
+    {
+        let start = cells.len();
+        for (x, c) in line.chars().enumerate() {
+            cells.push(decoder.decode(c).map_err(|error| FieldError::InvalidCell {
+                line: line_no,
+                column: x + 1,
+                cell: c,
+                error,
+            })?);
+        }
+        let found = cells.len() - start;
+        match *width.get_or_insert(found) {
+            expected if expected != found => Err(FieldError::RaggedRow {
+                line: line_no,
+                expected,
+                found,
+            }),
+            _ => Ok(()),
+        }
+    }
+}
+
+/// What a cell does when a chain of cells is pushed into it, see `Field::shift_chain`
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Shift {
+    // the cell is part of the chain and moves along with it
+    Carry,
+    // free space the chain moves into
+    Gap,
+    // the chain can't move
+    Wall,
+}
+
+impl<T> Field<T> {
+    // all of the below return false or None, and leave the field untouched, when out of bounds
+    pub fn set(&mut self, l: Location, val: T) -> bool {
+        self.get_mut(l).map(|cell| *cell = val).is_some()
+    }
+    // store a value, returning the one it replaced
+    pub fn replace(&mut self, l: Location, val: T) -> Option<T> {
+        self.get_mut(l).map(|cell| std::mem::replace(cell, val))
+    }
+    pub fn swap(&mut self, a: Location, b: Location) -> bool {
+        match (self.cartesian_to_index(a), self.cartesian_to_index(b)) {
+            (Some(a), Some(b)) => {
+                self.cells.swap(a, b);
+                true
+            }
+            _ => false,
+        }
+    }
+    // run the closure with the cell holding a value, then put the original value back
+    // i.e. try an extra obstacle without cloning the field
+    pub fn with_temporarily<R>(
+        &mut self,
+        l: Location,
+        val: T,
+        f: impl FnOnce(&mut Field<T>) -> R,
+    ) -> Option<R> {
+        let original = self.replace(l, val)?;
+        let out = f(self);
+        self.set(l, original);
+        Some(out)
+    }
+    // push the run of cells starting at start one step along the vector, as with boxes pushed by a robot.
+    // The chain extends up to the first gap, which ends up at start; nothing moves if a wall
+    // or the edge of the field comes first. Returns whether the chain moved
+    pub fn shift_chain(
+        &mut self,
+        start: Location,
+        dir: DirVector,
+        push: impl Fn(&T) -> Shift,
+    ) -> bool {
+        let mut chain = Vec::new();
+        let mut gap = false;
+        for (l, val) in self.ray(start, dir) {
+            chain.push(l);
+            match push(val) {
+                Shift::Carry => continue,
+                Shift::Gap => gap = true,
+                Shift::Wall => (),
+            }
+            break;
+        }
+        if gap {
+            // bubble the gap back to the start, moving every other cell one step forward
+            chain.windows(2).rev().for_each(|pair| {
+                self.swap(pair[0], pair[1]);
+            });
+        }
+        gap && chain.len() > 1
+    }
+}
+
+impl FromStr for Field<char> {
+    type Err = FieldError<Infallible>;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        Field::parse_with(s, Ok::<char, Infallible>)
+    }
 }
 
 impl FromStr for Field<u8> {
-    type Err = ();
+    type Err = FieldError<NotADigit>;
 
     fn from_str(s: &str) -> Result<Self, Self::Err> {
-        Ok(Field {
-            cells: s
-                .lines()
-                .map(|s| s.bytes().map(|c| c - b'0').collect::<Vec<u8>>())
-                .collect::<Vec<_>>(),
-        })
+        Field::parse_with(s, decode_digit)
+    }
+}
+
+// panics when the location is out of bounds, use get() otherwise
+impl<T> Index<Location> for Field<T> {
+    type Output = T;
+
+    fn index(&self, l: Location) -> &T {
+        let (w, h) = (self.width(), self.height());
+        self.get(l)
+            .unwrap_or_else(|| panic!("{l:?} is out of bounds for a {w}x{h} field"))
+    }
+}
+
+impl<T> IndexMut<Location> for Field<T> {
+    fn index_mut(&mut self, l: Location) -> &mut T {
+        let (w, h) = (self.width(), self.height());
+        self.get_mut(l)
+            .unwrap_or_else(|| panic!("{l:?} is out of bounds for a {w}x{h} field"))
     }
 }
 
@@ -82,7 +535,7 @@ where
 {
     fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
         write!(f, "W:{},H{}", self.width(), self.height())?;
-        for c in self.cells.iter() {
+        for c in self.rows() {
             writeln!(f)?;
             for c in c.iter() {
                 write!(f, "{c:2}")?
@@ -111,4 +564,331 @@ mod test {
         assert_eq!(field.get(Location(10, 9)), None);
         assert_eq!(field.get(Location(9, 10)), None);
     }
+
+    #[test]
+    fn test_get_far_away() {
+        let field = "ab\ncd".parse::<Field<char>>().expect("Doesn't error");
+
+        assert_eq!(field.get(Location(0, 1 << 63)), None);
+        assert_eq!(field.peek(Location(1, 1), (0, isize::MAX)), None);
+        assert_eq!(field.peek(Location(1, 1), (isize::MAX, 0)), None);
+    }
+
+    #[test]
+    fn test_row_column_views() {
+        let input = std::fs::read_to_string("src/bin/day4/sample.txt").expect("File not found");
+        let mut field = input.parse::<Field<char>>().expect("Doesn't error");
+
+        assert_eq!(field.rows().count(), field.height());
+        assert_eq!(
+            field.row(0),
+            Some(&['M', 'M', 'M', 'S', 'X', 'X', 'M', 'A', 'S', 'M'][..])
+        );
+        assert_eq!(field.row(10), None);
+        assert_eq!(
+            field.column(9).collect::<String>(),
+            (0..field.height())
+                .map(|y| field.get(Location(9, y)).unwrap())
+                .collect::<String>()
+        );
+        assert_eq!(field.column(10).count(), 0);
+        assert_eq!(field.get(Location(10, 0)), None);
+        assert_eq!(field.index_to_cartesian(23), Location(3, 2));
+
+        field.column_mut(0).for_each(|c| *c = '#');
+        field.row_mut(9).unwrap().fill('.');
+        assert_eq!(field.get(Location(0, 8)), Some(&'#'));
+        assert_eq!(field.get(Location(0, 9)), Some(&'.'));
+        assert_eq!(field.iter().filter(|&&c| c == '#').count(), 9);
+    }
+
+    #[test]
+    fn test_zero_width() {
+        let mut field = Field::new(0, 5, '.');
+        assert_eq!((field.width(), field.height()), (0, 5));
+        assert_eq!(field.rows().collect::<Vec<_>>(), vec![&[] as &[char]; 5]);
+        assert_eq!(field.rows_mut().count(), 5);
+        assert_eq!(field.row(4), Some(&[][..]));
+        assert_eq!(field.get(Location(0, 0)), None);
+        let field = Field::from_fn(3, 0, |_| 0);
+        assert_eq!(
+            (field.width(), field.height(), field.rows().count()),
+            (3, 0, 0)
+        );
+    }
+
+    #[test]
+    fn test_locations() {
+        let input = std::fs::read_to_string("src/bin/day8/sample.txt").expect("File not found");
+        let mut city = input.parse::<Field<char>>().expect("Doesn't error");
+
+        assert_eq!(city.iter_locations().count(), 144);
+        assert_eq!(city.iter_locations().nth(13), Some((Location(1, 1), &'.')));
+        assert_eq!(city.find(|&c| c == 'A'), Some(Location(6, 5)));
+        assert_eq!(city.find(|&c| c == '#'), None);
+        assert_eq!(
+            city.positions(|&c| c == 'A').collect::<Vec<_>>(),
+            [Location(6, 5), Location(8, 8), Location(9, 9)]
+        );
+
+        let groups = city.group_by_value();
+        assert_eq!(groups.len(), 3);
+        assert_eq!(groups[&'0'].len(), 4);
+        assert_eq!(groups[&'.'].len(), 144 - 7);
+        assert_eq!(
+            groups[&'A'],
+            city.positions(|&c| c == 'A').collect::<Vec<_>>()
+        );
+
+        city.iter_locations_mut()
+            .filter(|(l, _)| l.0 == l.1)
+            .for_each(|(_, c)| *c = '#');
+        assert_eq!(
+            city.positions(|&c| c == '#').collect::<Vec<_>>(),
+            (0..12).map(|i| Location(i, i)).collect::<Vec<_>>()
+        );
+    }
+
+    #[test]
+    fn test_histogram() {
+        let input = std::fs::read_to_string("src/bin/day8/sample.txt").expect("File not found");
+        let city = input.parse::<Field<char>>().expect("Doesn't error");
+
+        let histogram = city.histogram();
+        assert_eq!(histogram.len(), 3);
+        assert_eq!(
+            (histogram[&'0'], histogram[&'A'], histogram[&'.']),
+            (4, 3, 144 - 7)
+        );
+        let groups = city.group_by_value();
+        assert!(groups.iter().all(|(c, ls)| histogram[c] == ls.len()));
+    }
+
+    #[test]
+    fn test_neighbours() {
+        let input = std::fs::read_to_string("src/bin/day4/sample.txt").expect("File not found");
+        let mut field = input.parse::<Field<char>>().expect("Doesn't error");
+
+        assert_eq!(
+            field.neighbours4(Location(0, 0)).collect::<Vec<_>>(),
+            [
+                (Location(1, 0), (1, 0), &'M'),
+                (Location(0, 1), (0, 1), &'M')
+            ]
+        );
+        assert_eq!(field.neighbours4(Location(4, 4)).count(), 4);
+        assert_eq!(field.neighbours8(Location(4, 4)).count(), 8);
+        assert_eq!(field.neighbours8(Location(9, 9)).count(), 3);
+        assert_eq!(field.neighbours8(Location(10, 9)).count(), 2);
+        assert_eq!(
+            field
+                .neighbours(Location(0, 0), &[(2, 0), (0, 2), (-2, 0)])
+                .map(|(l, _, _)| l)
+                .collect::<Vec<_>>(),
+            [Location(2, 0), Location(0, 2)]
+        );
+
+        field
+            .neighbours8_mut(Location(1, 1))
+            .for_each(|(_, _, c)| *c = '#');
+        assert_eq!(field.iter().filter(|&&c| c == '#').count(), 8);
+        assert_eq!(
+            field
+                .neighbours_mut(Location(1, 1), &[(1, 1), (-1, -1), (0, 0)])
+                .map(|(l, d, _)| (l, d))
+                .collect::<Vec<_>>(),
+            [
+                (Location(2, 2), (1, 1)),
+                (Location(0, 0), (-1, -1)),
+                (Location(1, 1), (0, 0))
+            ]
+        );
+    }
+
+    #[test]
+    fn test_ray() {
+        let input = std::fs::read_to_string("src/bin/day6/sample.txt").expect("File not found");
+        let lab = input.parse::<Field<char>>().expect("Doesn't error");
+
+        assert_eq!(
+            lab.ray(Location(4, 6), (0, -1))
+                .map(|(_, &c)| c)
+                .collect::<String>(),
+            "^.....#"
+        );
+        assert_eq!(lab.ray(Location(4, 6), (1, 1)).count(), 4);
+        assert_eq!(lab.ray(Location(10, 0), (-1, 0)).count(), 0);
+        assert_eq!(
+            lab.first_hit(Location(4, 6), (0, -1), |&c| c == '#'),
+            Some((Location(4, 0), &'#'))
+        );
+        assert_eq!(lab.first_hit(Location(4, 6), (1, 0), |&c| c == '#'), None);
+        assert_eq!(lab.first_hit(Location(1, 6), (0, 0), |&c| c == '#'), None);
+    }
+
+    #[test]
+    fn test_from_reader() {
+        let file = std::fs::File::open("src/bin/day10/sample.txt").expect("File not found");
+        let map =
+            Field::from_reader(io::BufReader::new(file), decode_digit).expect("Doesn't error");
+        let input = std::fs::read_to_string("src/bin/day10/sample.txt").expect("File not found");
+        let parsed = input.parse::<Field<u8>>().expect("Doesn't error");
+        assert_eq!((map.width(), map.height()), (8, 8));
+        assert_eq!(map.as_slice(), parsed.as_slice());
+
+        // grid followed by a block of commands in the same stream
+        let mut input = "#.\r\n.#\r\n\r\n<>^\n".as_bytes();
+        let grid = Field::from_reader(&mut input, Ok::<char, Infallible>).expect("Doesn't error");
+        assert_eq!(grid.iter().collect::<String>(), "#..#");
+        let mut rest = String::new();
+        io::Read::read_to_string(&mut input, &mut rest).unwrap();
+        assert_eq!(rest, "<>^\n");
+
+        assert!(matches!(
+            Field::from_reader("12\n3x\n".as_bytes(), decode_digit),
+            Err(ReadError::Field(FieldError::InvalidCell {
+                line: 2,
+                column: 2,
+                cell: 'x',
+                ..
+            }))
+        ));
+        assert!(matches!(
+            Field::from_reader("12\n3\n".as_bytes(), decode_digit),
+            Err(ReadError::Field(FieldError::RaggedRow { line: 2, .. }))
+        ));
+        assert!(matches!(
+            Field::from_reader(&b"12\n\xff\n"[..], decode_digit),
+            Err(ReadError::Io { line: 2, .. })
+        ));
+        let empty = Field::from_reader("\n12".as_bytes(), decode_digit).expect("Doesn't error");
+        assert_eq!((empty.width(), empty.height()), (0, 0));
+    }
+
+    #[test]
+    fn test_mutation() {
+        let mut field = "ab\ncd".parse::<Field<char>>().expect("Doesn't error");
+
+        field[Location(0, 1)] = 'x';
+        assert_eq!(field[Location(0, 1)], 'x');
+        assert!(field.set(Location(1, 1), 'y'));
+        assert!(!field.set(Location(2, 1), 'y'));
+        assert_eq!(field.replace(Location(0, 0), 'z'), Some('a'));
+        assert_eq!(field.replace(Location(0, 2), 'z'), None);
+        assert!(field.swap(Location(0, 0), Location(1, 1)));
+        assert!(!field.swap(Location(0, 0), Location(0, 2)));
+        assert_eq!(field.iter().collect::<String>(), "ybxz");
+
+        let seen = field.with_temporarily(Location(1, 0), '#', |f| f.iter().collect::<String>());
+        assert_eq!(seen.as_deref(), Some("y#xz"));
+        assert_eq!(field.iter().collect::<String>(), "ybxz");
+        assert_eq!(field.with_temporarily(Location(5, 5), '#', |_| ()), None);
+    }
+
+    #[test]
+    #[should_panic(expected = "out of bounds")]
+    fn test_index_out_of_bounds() {
+        let field = Field::new(2, 2, 0);
+        let _ = field[Location(2, 0)];
+    }
+
+    #[test]
+    fn test_shift_chain() {
+        let mut warehouse = "#..OO.O#".parse::<Field<char>>().expect("Doesn't error");
+        let push = |&c: &char| match c {
+            'O' => Shift::Carry,
+            '.' => Shift::Gap,
+            _ => Shift::Wall,
+        };
+        let row = |f: &Field<char>| f.iter().collect::<String>();
+
+        assert!(warehouse.shift_chain(Location(3, 0), (1, 0), push));
+        assert_eq!(row(&warehouse), "#...OOO#");
+        // the boxes are up against the wall
+        assert!(!warehouse.shift_chain(Location(4, 0), (1, 0), push));
+        assert_eq!(row(&warehouse), "#...OOO#");
+        assert!(warehouse.shift_chain(Location(6, 0), (-1, 0), push));
+        assert_eq!(row(&warehouse), "#..OOO.#");
+        // a gap at the start moves nothing
+        assert!(!warehouse.shift_chain(Location(1, 0), (1, 0), push));
+        assert_eq!(row(&warehouse), "#..OOO.#");
+        // falling off the field is as good as a wall
+        let mut open = "OO".parse::<Field<char>>().expect("Doesn't error");
+        assert!(!open.shift_chain(Location(0, 0), (1, 0), push));
+    }
+
+    #[test]
+    fn test_wrapping() {
+        let input = std::fs::read_to_string("src/bin/day4/sample.txt").expect("File not found");
+        let field = input.parse::<Field<char>>().expect("Doesn't error");
+
+        assert_eq!(field.wrap(Location(0, 0), (-1, -1)), Some(Location(9, 9)));
+        assert_eq!(field.peek_wrapping(Location(0, 0), (-1, -1)), Some(&'X'));
+        assert_eq!(field.peek_wrapping(Location(9, 9), (10, 10)), Some(&'X'));
+        assert_eq!(
+            field.peek_wrapping(Location(0, 0), (1, 0)),
+            field.peek(Location(0, 0), (1, 0))
+        );
+        assert_eq!(
+            field
+                .neighbours_wrapping(Location(0, 0), &NEIGHBOURS8)
+                .map(|(l, _, _)| l)
+                .filter(|&l| field.within_bounds(l))
+                .count(),
+            8
+        );
+        assert_eq!(
+            Field::<char>::new(0, 0, '.').peek_wrapping(Location(0, 0), (1, 1)),
+            None
+        );
+    }
+
+    #[test]
+    fn test_parse_errors() {
+        let field = "0123\n4567".parse::<Field<u8>>().expect("Doesn't error");
+        assert_eq!(field.get(Location(3, 1)), Some(&7));
+
+        assert_eq!(
+            "0123\n45.7".parse::<Field<u8>>().err(),
+            Some(FieldError::InvalidCell {
+                line: 2,
+                column: 3,
+                cell: '.',
+                error: NotADigit
+            })
+        );
+        assert_eq!(
+            "....\n...\n....".parse::<Field<char>>().err(),
+            Some(FieldError::RaggedRow {
+                line: 2,
+                expected: 4,
+                found: 3
+            })
+        );
+
+        let field = Field::parse_with("#.#\n.#.", |c| match c {
+            '#' => Ok(true),
+            '.' => Ok(false),
+            _ => Err("unknown tile"),
+        })
+        .expect("Doesn't error");
+        assert_eq!(field.get(Location(1, 1)), Some(&true));
+
+        // trailing blank lines and sections after the grid are left alone, as with from_reader()
+        let field = "ab\ncd\n\n".parse::<Field<char>>().expect("Doesn't error");
+        assert_eq!((field.width(), field.height()), (2, 2));
+        let field = "ab\r\ncd\r\n\r\n<>^\nv\n"
+            .parse::<Field<char>>()
+            .expect("Doesn't error");
+        assert_eq!(field.iter().collect::<String>(), "abcd");
+        assert_eq!(
+            Field::parse_with("#?#", |c| match c {
+                '#' => Ok(true),
+                _ => Err("unknown tile"),
+            })
+            .err()
+            .map(|e| e.to_string()),
+            Some("line 1, column 2: cannot decode cell '?': unknown tile".to_string())
+        );
+    }
 }
@@ -0,0 +1,161 @@
+use crate::field::Field;
+use crate::location::{DirVector, Location};
+use std::collections::HashMap;
+
+/// Common access to bounded 2D grids, whether dense or sparse,
+/// so that algorithms can be written once against either representation
+pub trait Grid {
+    type Item;
+
+    fn width(&self) -> usize;
+    fn height(&self) -> usize;
+    fn get(&self, l: Location) -> Option<&Self::Item>;
+    fn get_mut(&mut self, l: Location) -> Option<&mut Self::Item>;
+    // every in-bounds cell along with its location, in row-major order
+    fn iter_locations(&self) -> impl Iterator<Item = (Location, &Self::Item)>;
+
+    fn within_bounds(&self, l: Location) -> bool {
+        l.0 < self.width() && l.1 < self.height()
+    }
+    fn peek(&self, l: Location, d: DirVector) -> Option<&Self::Item> {
+        l.move_relative(d).and_then(|l| self.get(l))
+    }
+}
+
+impl<T> Grid for Field<T> {
+    type Item = T;
+
+    fn width(&self) -> usize {
+        Field::width(self)
+    }
+    fn height(&self) -> usize {
+        Field::height(self)
+    }
+    fn get(&self, l: Location) -> Option<&T> {
+        Field::get(self, l)
+    }
+    fn get_mut(&mut self, l: Location) -> Option<&mut T> {
+        Field::get_mut(self, l)
+    }
+    fn iter_locations(&self) -> impl Iterator<Item = (Location, &T)> {
+        Field::iter_locations(self)
+    }
+}
+
+/// Bounded grid storing only the cells that differ from a default value.
+/// Suits mostly empty grids, i.e. a few antennas or obstacles over a large area
+#[derive(Debug, Clone)]
+pub struct SparseField<T> {
+    cells: HashMap<Location, T>,
+    default: T,
+    width: usize,
+    height: usize,
+}
+
+impl<T> SparseField<T> {
+    pub fn new(width: usize, height: usize, default: T) -> SparseField<T> {
+        SparseField {
+            cells: HashMap::new(),
+            default,
+            width,
+            height,
+        }
+    }
+    // keep only the cells of the field that differ from the default value
+    pub fn from_field(field: &Field<T>, default: T) -> SparseField<T>
+    where
+        T: Clone + PartialEq,
+    {
+        SparseField {
+            cells: field
+                .iter_locations()
+                .filter(|&(_, val)| *val != default)
+                .map(|(l, val)| (l, val.clone()))
+                .collect(),
+            default,
+            width: field.width(),
+            height: field.height(),
+        }
+    }
+    // only the cells explicitly stored, in no particular order
+    pub fn iter_stored(&self) -> impl Iterator<Item = (Location, &T)> {
+        self.cells.iter().map(|(&l, val)| (l, val))
+    }
+    // reset a cell back to the default value, returning what was stored
+    pub fn remove(&mut self, l: Location) -> Option<T> {
+        self.cells.remove(&l)
+    }
+    // number of cells explicitly stored
+    pub fn len(&self) -> usize {
+        self.cells.len()
+    }
+    pub fn is_empty(&self) -> bool {
+        self.cells.is_empty()
+    }
+}
+
+impl<T: Clone> Grid for SparseField<T> {
+    type Item = T;
+
+    fn width(&self) -> usize {
+        self.width
+    }
+    fn height(&self) -> usize {
+        self.height
+    }
+    fn get(&self, l: Location) -> Option<&T> {
+        self.within_bounds(l)
+            .then(|| self.cells.get(&l).unwrap_or(&self.default))
+    }
+    // writing through an unset cell stores a copy of the default value first
+    fn get_mut(&mut self, l: Location) -> Option<&mut T> {
+        self.within_bounds(l)
+            .then(|| self.cells.entry(l).or_insert_with(|| self.default.clone()))
+    }
+    fn iter_locations(&self) -> impl Iterator<Item = (Location, &T)> {
+        (0..self.height)
+            .flat_map(move |y| (0..self.width).map(move |x| Location(x, y)))
+            .map(|l| (l, self.cells.get(&l).unwrap_or(&self.default)))
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    // locations of all antennas grouped by frequency
+    fn antennas(city: &impl Grid<Item = char>) -> Vec<(char, Location)> {
+        let mut out = city
+            .iter_locations()
+            .filter(|&(_, &c)| c != '.')
+            .map(|(l, &c)| (c, l))
+            .collect::<Vec<_>>();
+        out.sort();
+        out
+    }
+
+    #[test]
+    fn test_dense_and_sparse() {
+        let input = std::fs::read_to_string("src/bin/day8/sample.txt").expect("File not found");
+        let dense = input.parse::<Field<char>>().expect("Doesn't error");
+        let mut sparse = SparseField::from_field(&dense, '.');
+
+        assert_eq!(sparse.len(), 7);
+        assert_eq!(antennas(&dense), antennas(&sparse));
+        assert_eq!(
+            dense.iter_locations().collect::<Vec<_>>(),
+            sparse.iter_locations().collect::<Vec<_>>()
+        );
+        assert_eq!((sparse.width(), sparse.height()), (12, 12));
+        assert_eq!(sparse.get(Location(11, 11)), Some(&'.'));
+        assert_eq!(sparse.get(Location(12, 11)), None);
+        assert_eq!(sparse.peek(Location(8, 2), (0, -1)), Some(&'0'));
+
+        *sparse.get_mut(Location(0, 0)).unwrap() = 'A';
+        assert_eq!(sparse.len(), 8);
+        assert_eq!(antennas(&sparse)[0], ('0', Location(4, 4)));
+        assert_eq!(sparse.remove(Location(0, 0)), Some('A'));
+        assert!(sparse.get_mut(Location(0, 12)).is_none());
+        assert_eq!(antennas(&dense), antennas(&sparse));
+    }
+}
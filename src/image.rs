@@ -0,0 +1,284 @@
+use crate::field::Field;
+use crate::location::Location;
+use std::io::{self, Read, Write};
+use thiserror::Error;
+
+/// Netpbm flavour to write; binary (P5/P6) is compact, ascii (P2/P3) is diffable text
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Encoding {
+    Binary,
+    Ascii,
+}
+
+#[derive(Debug, Error)]
+pub enum ImageError {
+    #[error(transparent)]
+    Io(#[from] io::Error),
+    #[error("unsupported image format {0:?}, expected P2 or P5")]
+    Format(String),
+    #[error("invalid header: {0}")]
+    Header(&'static str),
+    #[error("maximum grey value {0} is outside 1..=255")]
+    MaxValue(usize),
+    #[error("image data ends after {found} of {expected} pixels")]
+    Truncated { expected: usize, found: usize },
+}
+
+impl<T> Field<T> {
+    // every cell becomes a scale x scale block of pixels
+    fn pixels(&self, scale: usize) -> impl Iterator<Item = &T> {
+        let scale = scale.max(1);
+        (0..self.height() * scale).flat_map(move |y| {
+            (0..self.width() * scale).filter_map(move |x| self.get(Location(x / scale, y / scale)))
+        })
+    }
+
+    fn write_netpbm<const N: usize>(
+        &self,
+        out: &mut impl Write,
+        magic: &str,
+        encoding: Encoding,
+        scale: usize,
+        sample: impl Fn(&T) -> [u8; N],
+    ) -> io::Result<()> {
+        let scale = scale.max(1);
+        let width = self.width() * scale;
+        writeln!(out, "{magic}\n{width} {}\n255", self.height() * scale)?;
+        match encoding {
+            Encoding::Binary => {
+                let data = self.pixels(scale).flat_map(&sample).collect::<Vec<_>>();
+                out.write_all(&data)?;
+            }
+            Encoding::Ascii => {
+                // every image row starts a new line of text, wrapped at the 70 characters
+                // the plain format allows
+                const MAX_LINE: usize = 70;
+                let mut line = String::with_capacity(MAX_LINE);
+                for (i, px) in self.pixels(scale).enumerate() {
+                    for v in sample(px) {
+                        let v = v.to_string();
+                        if !line.is_empty() && line.len() + 1 + v.len() > MAX_LINE {
+                            writeln!(out, "{line}")?;
+                            line.clear();
+                        }
+                        if !line.is_empty() {
+                            line.push(' ');
+                        }
+                        line.push_str(&v);
+                    }
+                    if (i + 1) % width == 0 {
+                        writeln!(out, "{line}")?;
+                        line.clear();
+                    }
+                }
+            }
+        }
+        out.flush()
+    }
+
+    // colour image (PPM) with each cell mapped to an (r, g, b) colour
+    pub fn write_ppm(
+        &self,
+        out: &mut impl Write,
+        encoding: Encoding,
+        scale: usize,
+        colour: impl Fn(&T) -> (u8, u8, u8),
+    ) -> io::Result<()> {
+        let magic = match encoding {
+            Encoding::Binary => "P6",
+            Encoding::Ascii => "P3",
+        };
+        self.write_netpbm(out, magic, encoding, scale, |val| {
+            let (r, g, b) = colour(val);
+            [r, g, b]
+        })
+    }
+
+    // greyscale image (PGM) with each cell mapped to a 0..=255 intensity
+    pub fn write_pgm(
+        &self,
+        out: &mut impl Write,
+        encoding: Encoding,
+        scale: usize,
+        grey: impl Fn(&T) -> u8,
+    ) -> io::Result<()> {
+        let magic = match encoding {
+            Encoding::Binary => "P5",
+            Encoding::Ascii => "P2",
+        };
+        self.write_netpbm(out, magic, encoding, scale, |val| [grey(val)])
+    }
+}
+
+impl Field<u8> {
+    // load a binary (P5) or ascii (P2) greyscale image with one cell per pixel
+    pub fn read_pgm(mut input: impl Read) -> Result<Field<u8>, ImageError> {
+        let mut data = Vec::new();
+        input.read_to_end(&mut data)?;
+
+        // header tokens are separated by whitespace, '#' comments run to the end of the line
+        let mut pos = 0;
+        let mut token = || -> Option<&[u8]> {
+            loop {
+                while data.get(pos)?.is_ascii_whitespace() {
+                    pos += 1;
+                }
+                if data[pos] != b'#' {
+                    break;
+                }
+                while *data.get(pos)? != b'\n' {
+                    pos += 1;
+                }
+            }
+            let start = pos;
+            while data.get(pos).is_some_and(|c| !c.is_ascii_whitespace()) {
+                pos += 1;
+            }
+            Some(&data[start..pos])
+        };
+        let magic =
+            String::from_utf8_lossy(token().ok_or(ImageError::Header("missing magic number"))?)
+                .to_string();
+        let mut number = |what| {
+            token()
+                .and_then(|t| std::str::from_utf8(t).ok()?.parse::<usize>().ok())
+                .ok_or(ImageError::Header(what))
+        };
+
+        let width = number("missing width")?;
+        let height = number("missing height")?;
+        let max = number("missing maximum grey value")?;
+        if !(1..=255).contains(&max) {
+            return Err(ImageError::MaxValue(max));
+        }
+
+        let expected = width
+            .checked_mul(height)
+            .ok_or(ImageError::Header("image too large"))?;
+        let cells = match magic.as_str() {
+            "P2" => {
+                // stops short at the end of the data, caught as truncated below
+                let mut cells = Vec::new();
+                while cells.len() < expected {
+                    let Some(t) = token() else { break };
+                    let value = std::str::from_utf8(t)
+                        .ok()
+                        .and_then(|t| t.parse::<usize>().ok())
+                        .filter(|&v| v <= max)
+                        .ok_or(ImageError::Header("invalid pixel value"))?;
+                    cells.push(value as u8);
+                }
+                cells
+            }
+            // a single whitespace character separates the header from the pixel data
+            "P5" => data
+                .get(pos + 1..)
+                .unwrap_or_default()
+                .iter()
+                .take(expected)
+                .copied()
+                .collect::<Vec<_>>(),
+            _ => return Err(ImageError::Format(magic)),
+        };
+        if cells.len() != expected {
+            return Err(ImageError::Truncated {
+                expected,
+                found: cells.len(),
+            });
+        }
+        Ok(Field::from_cells(cells, width, height))
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn test_pgm_round_trip() {
+        let input = std::fs::read_to_string("src/bin/day10/sample.txt").expect("File not found");
+        let map = input.parse::<Field<u8>>().expect("Doesn't error");
+
+        for encoding in [Encoding::Binary, Encoding::Ascii] {
+            let mut buf = Vec::new();
+            map.write_pgm(&mut buf, encoding, 1, |&h| h * 25)
+                .expect("write to vec");
+            let image = Field::read_pgm(buf.as_slice()).expect("valid pgm");
+            assert_eq!(
+                image.iter().copied().collect::<Vec<_>>(),
+                map.iter().map(|&h| h * 25).collect::<Vec<_>>()
+            );
+
+            let mut buf = Vec::new();
+            map.write_pgm(&mut buf, encoding, 3, |&h| h)
+                .expect("write to vec");
+            let image = Field::read_pgm(buf.as_slice()).expect("valid pgm");
+            assert_eq!((image.width(), image.height()), (24, 24));
+            assert!((0..24).all(|y| {
+                (0..24).all(|x| image.get(Location(x, y)) == map.get(Location(x / 3, y / 3)))
+            }));
+        }
+    }
+
+    #[test]
+    fn test_ppm_and_errors() {
+        let field = "ab\nba".parse::<Field<char>>().expect("Doesn't error");
+        let colour = |&c: &char| if c == 'a' { (255, 0, 0) } else { (0, 0, 255) };
+
+        let mut buf = Vec::new();
+        field
+            .write_ppm(&mut buf, Encoding::Ascii, 1, colour)
+            .unwrap();
+        assert_eq!(
+            String::from_utf8(buf).unwrap(),
+            "P3\n2 2\n255\n255 0 0 0 0 255\n0 0 255 255 0 0\n"
+        );
+        let mut buf = Vec::new();
+        field
+            .write_ppm(&mut buf, Encoding::Binary, 2, colour)
+            .unwrap();
+        assert_eq!(&buf[..11], b"P6\n4 4\n255\n");
+        assert_eq!(buf.len(), 11 + 4 * 4 * 3);
+        assert_eq!(&buf[11..17], &[255, 0, 0, 255, 0, 0]);
+
+        // a 52 pixel row needs several lines of text
+        let wide = Field::new(52, 2, 'a');
+        let mut buf = Vec::new();
+        wide.write_ppm(&mut buf, Encoding::Ascii, 1, colour)
+            .unwrap();
+        let text = String::from_utf8(buf).unwrap();
+        assert!(text.lines().all(|l| l.len() <= 70));
+        // past the four header tokens, one value per colour channel
+        assert_eq!(text.split_ascii_whitespace().skip(4).count(), 52 * 2 * 3);
+
+        let pgm = b"P2\n# comment\n3 2\n255\n0 1 2\n3 4 5\n";
+        let image = Field::read_pgm(&pgm[..]).unwrap();
+        assert_eq!(image.get(Location(2, 1)), Some(&5));
+
+        assert!(matches!(
+            Field::read_pgm(&b"P3\n1 1\n255\n0 0 0"[..]),
+            Err(ImageError::Format(_))
+        ));
+        assert!(matches!(
+            Field::read_pgm(&b"P5\n2 2\n255\n\x01\x02\x03"[..]),
+            Err(ImageError::Truncated {
+                expected: 4,
+                found: 3
+            })
+        ));
+        assert!(matches!(
+            Field::read_pgm(&b"P2\n1 1\n65535\n0"[..]),
+            Err(ImageError::MaxValue(65535))
+        ));
+        assert!(matches!(
+            Field::read_pgm(&b"P2\n1 1\n5\n9"[..]),
+            Err(ImageError::Header("invalid pixel value"))
+        ));
+        assert!(matches!(
+            Field::read_pgm(&b"P5\n4294967296 4294967297\n255\n"[..]),
+            Err(ImageError::Header("image too large"))
+        ));
+        let image = Field::read_pgm(&b"P2\n0 3\n255\n"[..]).expect("valid pgm");
+        assert_eq!((image.width(), image.height()), (0, 3));
+    }
+}
@@ -0,0 +1,211 @@
+use crate::field::Field;
+use crate::location::Location;
+use std::fmt::{Debug, Display};
+
+/// Signed grid coordinates (x, y) with y growing downwards, as with `Location`
+pub type Coord = (isize, isize);
+
+/// Grid without bounds where every cell starts off holding the default value.
+///
+/// Cells are kept in a dense `Field` covering the region written so far,
+/// which is reallocated to a larger area whenever a write lands outside it.
+/// Every cell of the bounding box is allocated, so writes far apart get expensive;
+/// i.e. two writes 10^6 apart on both axes ask for 10^12 cells. Scattered points
+/// are better off in a `SparseField`
+pub struct InfiniteField<T> {
+    cells: Field<T>,
+    // coordinate of the top-left cell of the backing field
+    origin: Coord,
+    default: T,
+    // inclusive corners of the region written so far
+    bounds: Option<(Coord, Coord)>,
+}
+
+impl<T: Clone> InfiniteField<T> {
+    pub fn new(default: T) -> InfiniteField<T> {
+        InfiniteField {
+            cells: Field::new(0, 0, default.clone()),
+            origin: (0, 0),
+            default,
+            bounds: None,
+        }
+    }
+
+    fn to_location(&self, (x, y): Coord) -> Option<Location> {
+        let x = usize::try_from(x.checked_sub(self.origin.0)?).ok()?;
+        let y = usize::try_from(y.checked_sub(self.origin.1)?).ok()?;
+        Some(Location(x, y))
+    }
+
+    pub fn get(&self, c: Coord) -> &T {
+        self.to_location(c)
+            .and_then(|l| self.cells.get(l))
+            .unwrap_or(&self.default)
+    }
+
+    // writing outside the allocated area grows the grid
+    // panics when the region written would span more than isize::MAX cells along an axis
+    pub fn get_mut(&mut self, c: Coord) -> &mut T {
+        let (min, max) = match self.bounds {
+            None => (c, c),
+            Some((min, max)) => (
+                (min.0.min(c.0), min.1.min(c.1)),
+                (max.0.max(c.0), max.1.max(c.1)),
+            ),
+        };
+        if !self
+            .to_location(c)
+            .is_some_and(|l| self.cells.within_bounds(l))
+        {
+            self.grow(c);
+        }
+        self.bounds = Some((min, max));
+        let l = self.to_location(c).expect("coordinate within grown area");
+        self.cells.get_mut(l).expect("coordinate within grown area")
+    }
+
+    pub fn set(&mut self, c: Coord, val: T) {
+        *self.get_mut(c) = val;
+    }
+
+    // reallocate to cover the coordinate; the area grows by at least half its size
+    // towards the side it overflows so that a walk off the edge amortises
+    fn grow(&mut self, (x, y): Coord) {
+        let (ox, oy) = self.origin;
+        // returns the new start and length along one axis. Worked out in i128 as the end
+        // is one past the last cell and the margin may reach beyond the isize range
+        let extend = |pos: isize, start: isize, len: usize| -> (isize, usize) {
+            let (pos, start, len) = (pos as i128, start as i128, len as i128);
+            let margin = (len / 2).max(1);
+            let (min, end) = if len == 0 {
+                (pos, pos + 1)
+            } else if pos < start {
+                ((start - margin).min(pos), start + len)
+            } else if pos >= start + len {
+                (start, (start + len + margin).max(pos + 1))
+            } else {
+                (start, start + len)
+            };
+            let min = min.max(isize::MIN as i128);
+            let end = end.min(isize::MAX as i128 + 1);
+            let len = isize::try_from(end - min)
+                .expect("InfiniteField can't span more than isize::MAX cells along an axis");
+            (min as isize, len as usize)
+        };
+        let (min_x, w) = extend(x, ox, self.cells.width());
+        let (min_y, h) = extend(y, oy, self.cells.height());
+
+        let old = std::mem::replace(&mut self.cells, Field::new(0, 0, self.default.clone()));
+        self.cells = Field::from_fn(w, h, |Location(nx, ny)| {
+            let (cx, cy) = (min_x + nx as isize, min_y + ny as isize);
+            let offset = |c: isize, o: isize| usize::try_from(c.checked_sub(o)?).ok();
+            offset(cx, ox)
+                .zip(offset(cy, oy))
+                .and_then(|(x, y)| old.get(Location(x, y)))
+                .unwrap_or(&self.default)
+                .clone()
+        });
+        self.origin = (min_x, min_y);
+    }
+
+    // inclusive top-left and bottom-right corners of the region written so far
+    pub fn bounding_box(&self) -> Option<(Coord, Coord)> {
+        self.bounds
+    }
+
+    pub fn width(&self) -> usize {
+        self.bounds
+            .map(|(min, max)| (max.0 - min.0 + 1) as usize)
+            .unwrap_or(0)
+    }
+
+    pub fn height(&self) -> usize {
+        self.bounds
+            .map(|(min, max)| (max.1 - min.1 + 1) as usize)
+            .unwrap_or(0)
+    }
+
+    // copy of the region written so far; its top-left cell is the bounding box's top-left corner
+    pub fn to_field(&self) -> Field<T> {
+        let (min, _) = self.bounds.unwrap_or_default();
+        Field::from_fn(self.width(), self.height(), |Location(x, y)| {
+            self.get((min.0 + x as isize, min.1 + y as isize)).clone()
+        })
+    }
+}
+
+impl<T> Debug for InfiniteField<T>
+where
+    T: Clone + Debug + Display,
+{
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{:?}", self.to_field())
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::location::{turn_ccw, turn_cw};
+
+    #[test]
+    fn test_langtons_ant() {
+        let mut grid = InfiniteField::new('.');
+        let (mut pos, mut dir) = ((0, 0), (0, -1));
+
+        for _ in 0..6 {
+            let cell = grid.get_mut(pos);
+            (dir, *cell) = match cell {
+                '.' => (turn_cw(dir), '#'),
+                _ => (turn_ccw(dir), '.'),
+            };
+            pos = (pos.0 + dir.0, pos.1 + dir.1);
+        }
+
+        assert_eq!(pos, (-1, -1));
+        assert_eq!(grid.bounding_box(), Some(((-1, 0), (1, 1))));
+        assert_eq!((grid.width(), grid.height()), (3, 2));
+        assert_eq!(grid.get((-1, 0)), &'#');
+        assert_eq!(grid.get((0, 0)), &'.');
+        assert_eq!(grid.get((-100, 100)), &'.');
+        assert_eq!(
+            format!("{grid:?}"),
+            format!("{:?}", "#.#\n.##".parse::<Field<char>>().unwrap())
+        );
+    }
+
+    #[test]
+    fn test_growth() {
+        let mut grid = InfiniteField::new(0);
+        (-50..50).for_each(|i| grid.set((i, -i), i));
+        grid.set((300, -300), 7);
+
+        assert_eq!(grid.bounding_box(), Some(((-50, -300), (300, 50))));
+        assert!((-50..50).all(|i| *grid.get((i, -i)) == i));
+        assert_eq!(grid.get((300, -300)), &7);
+        assert_eq!(grid.get((0, 1)), &0);
+        assert_eq!(grid.to_field().iter().filter(|&&v| v != 0).count(), 100);
+    }
+
+    #[test]
+    fn test_extreme_coordinates() {
+        let mut grid = InfiniteField::new(0);
+        grid.set((isize::MAX, isize::MIN), 1);
+        grid.set((isize::MAX - 2, isize::MIN + 1), 2);
+        assert_eq!(
+            grid.bounding_box(),
+            Some(((isize::MAX - 2, isize::MIN), (isize::MAX, isize::MIN + 1)))
+        );
+        assert_eq!((grid.width(), grid.height()), (3, 2));
+        assert_eq!(grid.get((isize::MAX, isize::MIN)), &1);
+        assert_eq!(grid.get((isize::MIN, isize::MAX)), &0);
+    }
+
+    #[test]
+    #[should_panic(expected = "can't span more than isize::MAX cells")]
+    fn test_span_too_wide() {
+        let mut grid = InfiniteField::new(0);
+        grid.set((isize::MIN, 0), 1);
+        grid.set((isize::MAX, 0), 2);
+    }
+}
@@ -0,0 +1,218 @@
+use crate::field::Field;
+use crate::location::Location;
+use std::any::Any;
+
+// type-erased layer so that layers of different cell types can live side by side
+trait Layer {
+    fn cell(&self, l: Location) -> Option<&dyn Any>;
+    fn as_any(&self) -> &dyn Any;
+    fn as_any_mut(&mut self) -> &mut dyn Any;
+}
+
+impl<U: 'static> Layer for Field<U> {
+    fn cell(&self, l: Location) -> Option<&dyn Any> {
+        self.get(l).map(|val| val as &dyn Any)
+    }
+    fn as_any(&self) -> &dyn Any {
+        self
+    }
+    fn as_any_mut(&mut self) -> &mut dyn Any {
+        self
+    }
+}
+
+/// A static terrain field overlaid with any number of named layers,
+/// i.e. entities, costs or visit counts, all sharing the terrain's coordinates.
+/// Each layer is a `Field` of its own cell type, looked up by name and type
+pub struct LayeredField<T> {
+    terrain: Field<T>,
+    // kept in the order added
+    layers: Vec<(String, Box<dyn Layer>)>,
+}
+
+/// Everything found at one location, across the terrain and all layers
+pub struct LayeredCell<'a, T> {
+    pub location: Location,
+    pub terrain: &'a T,
+    layers: Vec<(&'a str, &'a dyn Any)>,
+}
+
+impl<'a, T> LayeredCell<'a, T> {
+    // None when there is no such layer or it holds another cell type
+    pub fn get<U: 'static>(&self, name: &str) -> Option<&'a U> {
+        self.layers
+            .iter()
+            .find(|(n, _)| *n == name)
+            .and_then(|(_, val)| val.downcast_ref())
+    }
+    pub fn names(&self) -> impl Iterator<Item = &'a str> + '_ {
+        self.layers.iter().map(|(n, _)| *n)
+    }
+}
+
+impl<T> LayeredField<T> {
+    pub fn new(terrain: Field<T>) -> LayeredField<T> {
+        LayeredField {
+            terrain,
+            layers: Vec::new(),
+        }
+    }
+    pub fn terrain(&self) -> &Field<T> {
+        &self.terrain
+    }
+    pub fn width(&self) -> usize {
+        self.terrain.width()
+    }
+    pub fn height(&self) -> usize {
+        self.terrain.height()
+    }
+
+    // adding a layer under an existing name replaces it
+    // panics when the layer doesn't cover the same area as the terrain
+    pub fn insert_layer<U: 'static>(&mut self, name: &str, layer: Field<U>) {
+        assert_eq!(
+            (layer.width(), layer.height()),
+            (self.width(), self.height()),
+            "layer {name:?} must cover the same area as the terrain"
+        );
+        match self.layers.iter_mut().find(|(n, _)| n == name) {
+            Some((_, existing)) => *existing = Box::new(layer),
+            None => self.layers.push((name.to_string(), Box::new(layer))),
+        }
+    }
+    // new layer with every cell holding the same value
+    pub fn add_layer<U: Clone + 'static>(&mut self, name: &str, default: U) {
+        self.insert_layer(name, Field::new(self.width(), self.height(), default));
+    }
+    pub fn remove_layer(&mut self, name: &str) -> bool {
+        let before = self.layers.len();
+        self.layers.retain(|(n, _)| n != name);
+        self.layers.len() != before
+    }
+    // None when there is no such layer or it holds another cell type
+    pub fn layer<U: 'static>(&self, name: &str) -> Option<&Field<U>> {
+        self.layers
+            .iter()
+            .find(|(n, _)| n == name)
+            .and_then(|(_, layer)| layer.as_any().downcast_ref())
+    }
+    pub fn layer_mut<U: 'static>(&mut self, name: &str) -> Option<&mut Field<U>> {
+        self.layers
+            .iter_mut()
+            .find(|(n, _)| n == name)
+            .and_then(|(_, layer)| layer.as_any_mut().downcast_mut())
+    }
+    pub fn names(&self) -> impl Iterator<Item = &str> {
+        self.layers.iter().map(|(n, _)| n.as_str())
+    }
+
+    // the terrain and every layer at a location
+    pub fn at(&self, l: Location) -> Option<LayeredCell<'_, T>> {
+        Some(LayeredCell {
+            location: l,
+            terrain: self.terrain.get(l)?,
+            layers: self
+                .layers
+                .iter()
+                .filter_map(|(n, layer)| layer.cell(l).map(|val| (n.as_str(), val)))
+                .collect(),
+        })
+    }
+
+    // move the entity held at `from` in an entity layer (cells of `Option<U>`) over to `to`.
+    // The move goes ahead only when there is an entity to move, the destination is free
+    // within the same layer and `blocked` doesn't object to the destination cell;
+    // i.e. a wall in the terrain or a crate in another layer. Returns whether it moved
+    pub fn move_entity<U: 'static>(
+        &mut self,
+        layer: &str,
+        from: Location,
+        to: Location,
+        blocked: impl Fn(&LayeredCell<'_, T>) -> bool,
+    ) -> bool {
+        let can_move = self.layer::<Option<U>>(layer).is_some_and(|entities| {
+            matches!(entities.get(from), Some(Some(_))) && matches!(entities.get(to), Some(None))
+        }) && self.at(to).is_some_and(|cell| !blocked(&cell));
+        if can_move {
+            let entities = self
+                .layer_mut::<Option<U>>(layer)
+                .expect("layer checked above");
+            let entity = entities.replace(from, None).flatten();
+            entities.set(to, entity);
+        }
+        can_move
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::location::DirVector;
+
+    #[test]
+    fn test_layers() {
+        let input = std::fs::read_to_string("src/bin/day6/sample.txt").expect("File not found");
+        let lab = input.parse::<Field<char>>().expect("Doesn't error");
+        let start = lab.find(|&c| c == '^').expect("there is a guard");
+
+        let mut world = LayeredField::new(lab);
+        world.add_layer::<Option<DirVector>>("guard", None);
+        world.add_layer("visits", 0_usize);
+        world.layer_mut::<Option<DirVector>>("guard").unwrap()[start] = Some((0, -1));
+        world.layer_mut::<usize>("visits").unwrap()[start] += 1;
+
+        assert_eq!(world.names().collect::<Vec<_>>(), ["guard", "visits"]);
+        assert!(world.layer::<u8>("visits").is_none());
+        assert!(world.layer::<usize>("costs").is_none());
+
+        let cell = world.at(start).expect("within bounds");
+        assert_eq!(cell.terrain, &'^');
+        assert_eq!(cell.get::<Option<DirVector>>("guard"), Some(&Some((0, -1))));
+        assert_eq!(cell.get::<usize>("visits"), Some(&1));
+        assert_eq!(cell.get::<char>("visits"), None);
+        assert!(world.at(Location(10, 0)).is_none());
+
+        // walk the guard up until the obstacle stops it
+        let wall = |cell: &LayeredCell<'_, char>| *cell.terrain == '#';
+        let mut pos = start;
+        while let Some(next) = pos.move_relative((0, -1)) {
+            if !world.move_entity::<DirVector>("guard", pos, next, wall) {
+                break;
+            }
+            world.layer_mut::<usize>("visits").unwrap()[next] += 1;
+            pos = next;
+        }
+        assert_eq!(pos, Location(4, 1));
+        assert_eq!(
+            world
+                .layer::<usize>("visits")
+                .unwrap()
+                .iter()
+                .sum::<usize>(),
+            6
+        );
+        assert_eq!(
+            world.at(start).unwrap().get::<Option<DirVector>>("guard"),
+            Some(&None)
+        );
+
+        // nothing to move, wrong layer type and out of bounds
+        assert!(!world.move_entity::<DirVector>("guard", start, Location(4, 5), |_| false));
+        assert!(!world.move_entity::<usize>("guard", pos, Location(3, 1), |_| false));
+        assert!(!world.move_entity::<DirVector>("guard", pos, Location(10, 1), |_| false));
+
+        assert!(world.remove_layer("visits"));
+        assert!(!world.remove_layer("visits"));
+        assert_eq!(
+            world.at(pos).unwrap().names().collect::<Vec<_>>(),
+            ["guard"]
+        );
+    }
+
+    #[test]
+    #[should_panic(expected = "same area")]
+    fn test_layer_size() {
+        let mut world = LayeredField::new(Field::new(3, 3, '.'));
+        world.insert_layer("costs", Field::new(3, 2, 0));
+    }
+}
@@ -1,5 +1,23 @@
+pub mod bitset;
+pub mod components;
+pub mod contour;
+pub mod cycle;
+pub mod diff;
 pub mod field;
+pub mod grid;
+pub mod image;
+pub mod infinite;
+pub mod layered;
 pub mod location;
+#[cfg(feature = "parallel")]
+pub mod parallel;
+pub mod pattern;
+pub mod render;
+pub mod search;
+#[cfg(feature = "serde")]
+mod serialize;
+pub mod vector;
+pub mod view;
 
 // id generator fn()
 pub fn id_generator(mut start: usize) -> impl FnMut() -> usize {
@@ -1,13 +1,47 @@
+use crate::vector::Vector;
 use std::ops::Sub;
 
 pub type DirVector = (isize, isize);
 
-pub fn turn_cw(d: DirVector) -> DirVector {
-    Direction::from(d).turn_cw().to_cartesian()
+// up, right, down, left
+pub const NEIGHBOURS4: [DirVector; 4] = [(0, -1), (1, 0), (0, 1), (-1, 0)];
+// clockwise starting from up
+pub const NEIGHBOURS8: [DirVector; 8] = [
+    (0, -1),
+    (1, -1),
+    (1, 0),
+    (1, 1),
+    (0, 1),
+    (-1, 1),
+    (-1, 0),
+    (-1, -1),
+];
+
+/// Which cells count as adjacent when expanding over a grid
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Connectivity {
+    // up, right, down, left
+    Four,
+    // including diagonals
+    Eight,
 }
 
-pub fn turn_ccw(d: DirVector) -> DirVector {
-    Direction::from(d).turn_ccw().to_cartesian()
+impl Connectivity {
+    pub fn stencil(&self) -> &'static [DirVector] {
+        match self {
+            Connectivity::Four => &NEIGHBOURS4,
+            Connectivity::Eight => &NEIGHBOURS8,
+        }
+    }
+}
+
+// work with either a DirVector or a Vector, handing back the same type
+pub fn turn_cw<V: Into<DirVector> + From<DirVector>>(d: V) -> V {
+    Direction::from(d.into()).turn_cw().to_cartesian().into()
+}
+
+pub fn turn_ccw<V: Into<DirVector> + From<DirVector>>(d: V) -> V {
+    Direction::from(d.into()).turn_ccw().to_cartesian().into()
 }
 
 pub fn dirvector_to_char(d: DirVector) -> char {
@@ -18,6 +52,7 @@ pub fn reverse_dirvector(d: DirVector) -> DirVector {
     (-d.0, -d.1)
 }
 
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub enum Direction {
     Left,
     Down,
@@ -76,17 +111,54 @@ impl From<DirVector> for Direction {
     }
 }
 
+impl From<Vector> for Direction {
+    fn from(value: Vector) -> Self {
+        DirVector::from(value).into()
+    }
+}
+
+impl From<Direction> for Vector {
+    fn from(value: Direction) -> Self {
+        value.to_cartesian().into()
+    }
+}
+
 #[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, PartialOrd, Ord)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub struct Location(pub usize, pub usize);
 
 impl Location {
     // get a new location given current location + delta vector
-    pub fn move_relative(&self, distance: DirVector) -> Option<Location> {
+    pub fn move_relative(&self, distance: impl Into<DirVector>) -> Option<Location> {
+        let distance = distance.into();
         let x = self.0.checked_add_signed(distance.0);
         let y = self.1.checked_add_signed(distance.1);
         x.zip(y).map(|(x, y)| Location(x, y))
     }
 
+    // new location given current location + delta vector on a torus of width x height,
+    // so that stepping off one edge re-enters from the opposite one
+    // panics if either of width or height is zero
+    pub fn move_wrapping(&self, distance: DirVector, (width, height): (usize, usize)) -> Location {
+        // widen to i128 so that large steps (i.e. velocity * steps) can never overflow
+        let wrap = |pos: usize, delta: isize, len: usize| {
+            (pos as i128 + delta as i128).rem_euclid(len as i128) as usize
+        };
+        Location(
+            wrap(self.0, distance.0, width),
+            wrap(self.1, distance.1, height),
+        )
+    }
+
+    // this location followed by every location one more step along the vector,
+    // until the coordinates can no longer be represented;
+    // a zero vector yields this location only
+    pub fn ray(self, dir: DirVector) -> impl Iterator<Item = Location> {
+        std::iter::successors(Some(self), move |l| {
+            (dir != (0, 0)).then(|| l.move_relative(dir)).flatten()
+        })
+    }
+
     pub fn next(&self, distance: Direction) -> Option<Location> {
         self.move_relative(distance.to_cartesian())
     }
@@ -132,4 +204,44 @@ mod test {
             Some(Location(9223372036854775808_usize, 1))
         );
     }
+
+    #[test]
+    fn test_ray() {
+        assert_eq!(
+            Location(2, 1).ray((-1, 1)).collect::<Vec<_>>(),
+            [Location(2, 1), Location(1, 2), Location(0, 3)]
+        );
+        assert_eq!(Location(2, 1).ray((0, 0)).count(), 1);
+        assert_eq!(Location(0, 0).ray((3, 2)).nth(4), Some(Location(12, 8)));
+    }
+
+    #[test]
+    fn test_move_wrapping() {
+        let bounds = (11, 7);
+        assert_eq!(Location(1, 1).move_wrapping((0, 0), bounds), Location(1, 1));
+        assert_eq!(
+            Location(0, 0).move_wrapping((-1, -1), bounds),
+            Location(10, 6)
+        );
+        assert_eq!(
+            Location(10, 6).move_wrapping((1, 1), bounds),
+            Location(0, 0)
+        );
+        // robot at p=2,4 with v=2,-3 after 5 seconds
+        assert_eq!(
+            Location(2, 4).move_wrapping((2 * 5, -3 * 5), bounds),
+            Location(1, 3)
+        );
+        assert_eq!(
+            Location(2, 4).move_wrapping((2 * 100, -3 * 100), bounds),
+            Location((2 + 200) % 11, (4 + 7 * 100 - 300) % 7)
+        );
+        assert_eq!(
+            Location(5, 5).move_wrapping((isize::MIN, isize::MAX), bounds),
+            Location(
+                (5 + isize::MIN as i128).rem_euclid(11) as usize,
+                (5 + isize::MAX as i128).rem_euclid(7) as usize
+            )
+        );
+    }
 }
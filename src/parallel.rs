@@ -0,0 +1,81 @@
+use crate::field::Field;
+use crate::location::Location;
+use rayon::iter::Either;
+use rayon::prelude::*;
+
+// Parallel counterparts of the serial traversals; every one of them
+// visits, yields or produces cells in exactly the same order as the serial path
+impl<T: Sync> Field<T> {
+    pub fn par_rows(&self) -> impl IndexedParallelIterator<Item = &[T]> {
+        let (width, cells) = (self.width(), self.as_slice());
+        (0..self.height())
+            .into_par_iter()
+            .map(move |y| &cells[y * width..(y + 1) * width])
+    }
+
+    pub fn par_iter_locations(&self) -> impl IndexedParallelIterator<Item = (Location, &T)> {
+        self.as_slice()
+            .par_iter()
+            .enumerate()
+            .map(|(idx, val)| (self.index_to_cartesian(idx), val))
+    }
+
+    // new field of the same dimensions with every cell computed in parallel
+    pub fn par_map<U: Send>(&self, f: impl Fn(Location, &T) -> U + Sync + Send) -> Field<U> {
+        let cells = self
+            .par_iter_locations()
+            .map(|(l, val)| f(l, val))
+            .collect::<Vec<_>>();
+        Field::from_cells(cells, self.width(), self.height())
+    }
+}
+
+impl<T: Send> Field<T> {
+    pub fn par_rows_mut(&mut self) -> impl IndexedParallelIterator<Item = &mut [T]> {
+        let (width, height) = (self.width(), self.height());
+        match width {
+            // chunking can't hand out the empty rows of a field without columns
+            0 => Either::Left((0..height).into_par_iter().map(|_| <&mut [T]>::default())),
+            _ => Either::Right(self.as_mut_slice().par_chunks_mut(width)),
+        }
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn test_parallel_matches_serial() {
+        let input = std::fs::read_to_string("src/bin/day6/input.txt").expect("File not found");
+        let mut lab = input.parse::<Field<char>>().expect("Doesn't error");
+
+        assert_eq!(
+            lab.par_rows().collect::<Vec<_>>(),
+            lab.rows().collect::<Vec<_>>()
+        );
+        let serial = lab
+            .iter()
+            .enumerate()
+            .map(|(i, c)| (lab.index_to_cartesian(i), c))
+            .collect::<Vec<_>>();
+        assert_eq!(lab.par_iter_locations().collect::<Vec<_>>(), serial);
+
+        // count obstacles around every cell
+        let count =
+            |l: Location, _: &char| lab.neighbours8(l).filter(|&(_, _, &c)| c == '#').count();
+        let parallel = lab.par_map(count);
+        let serial = Field::from_fn(lab.width(), lab.height(), |l| count(l, &'.'));
+        assert_eq!(
+            (parallel.width(), parallel.height()),
+            (lab.width(), lab.height())
+        );
+        assert_eq!(parallel.as_slice(), serial.as_slice());
+
+        lab.par_rows_mut().for_each(|row| row.reverse());
+        assert_eq!(
+            lab.row(0).map(|r| r[0]),
+            input.lines().next().unwrap().chars().last()
+        );
+    }
+}
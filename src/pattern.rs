@@ -0,0 +1,212 @@
+use crate::field::{Field, FieldError};
+use crate::location::Location;
+use crate::view::FieldView;
+use std::convert::Infallible;
+
+/// The eight ways a template can be laid onto a grid;
+/// flipped orientations mirror the template left to right before rotating it clockwise
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub enum Orientation {
+    Identity,
+    Rotate90,
+    Rotate180,
+    Rotate270,
+    Flip,
+    FlipRotate90,
+    FlipRotate180,
+    FlipRotate270,
+}
+
+impl Orientation {
+    pub const ALL: [Orientation; 8] = [
+        Orientation::Identity,
+        Orientation::Rotate90,
+        Orientation::Rotate180,
+        Orientation::Rotate270,
+        Orientation::Flip,
+        Orientation::FlipRotate90,
+        Orientation::FlipRotate180,
+        Orientation::FlipRotate270,
+    ];
+
+    pub fn apply<'a, T>(&self, view: FieldView<'a, T>) -> FieldView<'a, T> {
+        match self {
+            Orientation::Identity => view,
+            Orientation::Rotate90 => view.rotate90(),
+            Orientation::Rotate180 => view.rotate180(),
+            Orientation::Rotate270 => view.rotate270(),
+            Orientation::Flip => view.flip_horizontal(),
+            Orientation::FlipRotate90 => view.flip_horizontal().rotate90(),
+            Orientation::FlipRotate180 => view.flip_horizontal().rotate180(),
+            Orientation::FlipRotate270 => view.flip_horizontal().rotate270(),
+        }
+    }
+}
+
+/// Small 2D template where `None` cells match anything
+pub struct Pattern<T> {
+    // the distinct orientations to try, each with its own copy of the template
+    templates: Vec<(Orientation, Field<Option<T>>)>,
+}
+
+/// A place where the pattern fits, its top-left corner at `anchor`
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct PatternMatch {
+    pub anchor: Location,
+    pub orientation: Orientation,
+}
+
+impl<T: Clone + PartialEq> Pattern<T> {
+    pub fn new(template: Field<Option<T>>) -> Pattern<T> {
+        Pattern {
+            templates: vec![(Orientation::Identity, template)],
+        }
+    }
+
+    // also try every rotation and reflection of the template;
+    // orientations that look the same as an earlier one are tried only once,
+    // so that symmetric patterns aren't reported more than once per place
+    pub fn all_orientations(self) -> Pattern<T> {
+        let (_, template) = &self.templates[0];
+        let mut templates: Vec<(Orientation, Field<Option<T>>)> = Vec::with_capacity(8);
+        for o in Orientation::ALL {
+            let oriented = o.apply(template.view()).to_owned();
+            let seen = templates.iter().any(|(_, t)| {
+                (t.width(), t.height()) == (oriented.width(), oriented.height())
+                    && t.iter().eq(oriented.iter())
+            });
+            if !seen {
+                templates.push((o, oriented));
+            }
+        }
+        Pattern { templates }
+    }
+}
+
+impl Pattern<char> {
+    // one template row per line, with the wildcard character matching anything
+    pub fn parse(s: &str, wildcard: char) -> Result<Pattern<char>, FieldError<Infallible>> {
+        Field::parse_with(s, |c| Ok::<_, Infallible>((c != wildcard).then_some(c)))
+            .map(Pattern::new)
+    }
+}
+
+impl<T: PartialEq> Field<T> {
+    fn matches_at(&self, template: &Field<Option<T>>, Location(ax, ay): Location) -> bool {
+        (0..template.height()).all(|y| {
+            (0..template.width()).all(|x| match template.get(Location(x, y)) {
+                Some(Some(expected)) => self.get(Location(ax + x, ay + y)) == Some(expected),
+                _ => true,
+            })
+        })
+    }
+
+    // every place and orientation the pattern fits, ordered by anchor;
+    // a template without any cells fits nowhere
+    pub fn find_pattern(&self, pattern: &Pattern<T>) -> Vec<PatternMatch> {
+        let mut found = pattern
+            .templates
+            .iter()
+            .filter(|(_, template)| template.width() > 0 && template.height() > 0)
+            .flat_map(|(orientation, template)| {
+                let xs = (self.width() + 1).saturating_sub(template.width());
+                let ys = (self.height() + 1).saturating_sub(template.height());
+                (0..ys)
+                    .flat_map(move |y| (0..xs).map(move |x| Location(x, y)))
+                    .filter(|&anchor| self.matches_at(template, anchor))
+                    .map(|anchor| PatternMatch {
+                        anchor,
+                        orientation: *orientation,
+                    })
+            })
+            .collect::<Vec<_>>();
+        found.sort();
+        found
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn test_x_mas() {
+        let input = std::fs::read_to_string("src/bin/day4/sample.txt").expect("File not found");
+        let field = input.parse::<Field<char>>().expect("Doesn't error");
+
+        let x_mas = Pattern::parse("M.S\n.A.\nM.S", '.').expect("Doesn't error");
+        let found = field.find_pattern(&x_mas);
+        assert_eq!(found.len(), 2);
+        assert!(found.iter().all(|m| m.orientation == Orientation::Identity));
+
+        // reflections of the cross coincide with its rotations
+        let x_mas = x_mas.all_orientations();
+        assert_eq!(x_mas.templates.len(), 4);
+        let found = field.find_pattern(&x_mas);
+        assert_eq!(found.len(), 9);
+        assert_eq!(
+            found[0],
+            PatternMatch {
+                anchor: Location(0, 6),
+                orientation: Orientation::Rotate270
+            }
+        );
+    }
+
+    #[test]
+    fn test_orientations() {
+        let field = "....\n.ab.\n..c.\n....".parse::<Field<char>>().unwrap();
+        let pattern = Pattern::parse("ab\n.c", '.').unwrap().all_orientations();
+        assert_eq!(pattern.templates.len(), 8);
+        assert_eq!(
+            field.find_pattern(&pattern),
+            [PatternMatch {
+                anchor: Location(1, 1),
+                orientation: Orientation::Identity
+            }]
+        );
+
+        let rotated = field.view().rotate90().to_owned();
+        assert_eq!(
+            rotated.find_pattern(&pattern),
+            [PatternMatch {
+                anchor: Location(1, 1),
+                orientation: Orientation::Rotate90
+            }]
+        );
+        assert_eq!(
+            field
+                .view()
+                .flip_horizontal()
+                .to_owned()
+                .find_pattern(&pattern)[0]
+                .orientation,
+            Orientation::Flip
+        );
+        assert!(
+            field
+                .find_pattern(&Pattern::parse("abcde", '.').unwrap())
+                .is_empty()
+        );
+    }
+
+    #[test]
+    fn test_empty_template() {
+        let field = "ab".parse::<Field<char>>().unwrap();
+        assert!(
+            field
+                .find_pattern(&Pattern::parse("", '.').unwrap().all_orientations())
+                .is_empty()
+        );
+        assert!(
+            field
+                .find_pattern(&Pattern::new(Field::new(0, 1, None)))
+                .is_empty()
+        );
+        assert!(
+            field
+                .find_pattern(&Pattern::new(Field::new(1, 0, None)))
+                .is_empty()
+        );
+    }
+}
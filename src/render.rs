@@ -0,0 +1,288 @@
+use crate::field::Field;
+use crate::location::Location;
+pub use colored::Color;
+use std::{
+    collections::{HashMap, HashSet},
+    fmt::{Display, Write},
+};
+use unicode_width::UnicodeWidthStr;
+
+/// How a single cell is drawn; unset parts are left to the layers underneath
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct CellStyle {
+    pub glyph: Option<String>,
+    pub fg: Option<Color>,
+    pub bg: Option<Color>,
+}
+
+impl CellStyle {
+    pub fn glyph(glyph: impl Display) -> CellStyle {
+        CellStyle {
+            glyph: Some(glyph.to_string()),
+            ..Default::default()
+        }
+    }
+    pub fn fg(self, colour: Color) -> CellStyle {
+        CellStyle {
+            fg: Some(colour),
+            ..self
+        }
+    }
+    pub fn bg(self, colour: Color) -> CellStyle {
+        CellStyle {
+            bg: Some(colour),
+            ..self
+        }
+    }
+    // paint `top` over self; anything `top` leaves unset shows through
+    fn overlay(self, top: CellStyle) -> CellStyle {
+        CellStyle {
+            glyph: top.glyph.or(self.glyph),
+            fg: top.fg.or(self.fg),
+            bg: top.bg.or(self.bg),
+        }
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Output {
+    // ANSI colour escape sequences for terminals
+    Ansi,
+    // glyphs only, colours stripped
+    Plain,
+}
+
+type Paint<'a> = Box<dyn Fn(Location) -> Option<CellStyle> + 'a>;
+
+struct Layer<'a> {
+    name: String,
+    legend: Option<CellStyle>,
+    paint: Paint<'a>,
+}
+
+/// Draws a `Field` with any number of overlay layers stacked on top of it,
+/// followed by a legend naming each layer.
+///
+/// Layers are painted in the order they are added, the last one ending on top
+pub struct Renderer<'a, T> {
+    field: &'a Field<T>,
+    base: Box<dyn Fn(&T) -> CellStyle + 'a>,
+    layers: Vec<Layer<'a>>,
+    cell_width: usize,
+}
+
+impl<'a, T: Display> Renderer<'a, T> {
+    // cells are drawn using their Display output
+    pub fn new(field: &'a Field<T>) -> Renderer<'a, T> {
+        Renderer::with_style(field, |val: &T| CellStyle::glyph(val))
+    }
+}
+
+impl<'a, T> Renderer<'a, T> {
+    pub fn with_style(field: &'a Field<T>, base: impl Fn(&T) -> CellStyle + 'a) -> Renderer<'a, T> {
+        Renderer {
+            field,
+            base: Box::new(base),
+            layers: Vec::new(),
+            cell_width: 1,
+        }
+    }
+
+    // pad every glyph to the given number of terminal columns; wide glyphs take two
+    pub fn cell_width(self, cell_width: usize) -> Self {
+        Renderer { cell_width, ..self }
+    }
+
+    // paint with a function returning the style of a location, if any
+    pub fn layer(
+        mut self,
+        name: &str,
+        legend: Option<CellStyle>,
+        paint: impl Fn(Location) -> Option<CellStyle> + 'a,
+    ) -> Self {
+        self.layers.push(Layer {
+            name: name.to_string(),
+            legend,
+            paint: Box::new(paint),
+        });
+        self
+    }
+
+    // paint every location of the set with the same style
+    pub fn locations(
+        self,
+        name: &str,
+        locations: impl IntoIterator<Item = Location>,
+        style: CellStyle,
+    ) -> Self {
+        let set = locations.into_iter().collect::<HashSet<_>>();
+        let legend = style.clone();
+        self.layer(name, Some(legend), move |l| {
+            set.contains(&l).then(|| style.clone())
+        })
+    }
+
+    // replace the glyph of selected locations
+    pub fn glyphs<G: Display>(
+        self,
+        name: &str,
+        glyphs: impl IntoIterator<Item = (Location, G)>,
+    ) -> Self {
+        let map = glyphs
+            .into_iter()
+            .map(|(l, g)| (l, g.to_string()))
+            .collect::<HashMap<_, _>>();
+        self.layer(name, None, move |l| map.get(&l).map(CellStyle::glyph))
+    }
+
+    // colour the background of selected locations
+    pub fn colours(self, name: &str, colours: impl IntoIterator<Item = (Location, Color)>) -> Self {
+        let map = colours.into_iter().collect::<HashMap<_, _>>();
+        self.layer(name, None, move |l| {
+            map.get(&l).map(|&c| CellStyle::default().bg(c))
+        })
+    }
+
+    fn style_at(&self, l: Location) -> CellStyle {
+        let base = self.field.get(l).map(&self.base).unwrap_or_default();
+        self.layers
+            .iter()
+            .filter_map(|layer| (layer.paint)(l))
+            .fold(base, CellStyle::overlay)
+    }
+
+    fn draw(&self, buf: &mut String, style: &CellStyle, output: Output) -> std::fmt::Result {
+        let glyph = style.glyph.as_deref().unwrap_or(" ");
+        let codes = match output {
+            Output::Plain => None,
+            Output::Ansi => Some(
+                style
+                    .fg
+                    .map(fg_code)
+                    .into_iter()
+                    .chain(style.bg.map(bg_code))
+                    .collect::<Vec<_>>()
+                    .join(";"),
+            ),
+        };
+        // format padding counts chars, which misaligns wide glyphs i.e. emoji
+        let pad = self.cell_width.saturating_sub(glyph.width());
+        match codes.filter(|c| !c.is_empty()) {
+            Some(codes) => write!(buf, "\x1B[{codes}m{glyph}{:1$}\x1B[0m", "", pad),
+            None => write!(buf, "{glyph}{:1$}", "", pad),
+        }
+    }
+
+    pub fn render(&self, output: Output) -> String {
+        let mut buf = String::with_capacity(self.field.width() * self.field.height() * 2);
+        for y in 0..self.field.height() {
+            for x in 0..self.field.width() {
+                self.draw(&mut buf, &self.style_at(Location(x, y)), output)
+                    .ok();
+            }
+            buf.push('\n');
+        }
+        for layer in &self.layers {
+            match &layer.legend {
+                Some(style) => self.draw(&mut buf, style, output).ok(),
+                None => write!(buf, "{:1$}", "", self.cell_width).ok(),
+            };
+            writeln!(buf, " {}", layer.name).ok();
+        }
+        buf
+    }
+}
+
+// colored swaps TrueColor for the nearest basic colour unless COLORTERM asks for 24-bit,
+// which makes distinct colours collide; always write the 24-bit sequence instead
+fn fg_code(colour: Color) -> String {
+    match colour {
+        Color::TrueColor { r, g, b } => format!("38;2;{r};{g};{b}"),
+        basic => basic.to_fg_str().into_owned(),
+    }
+}
+
+fn bg_code(colour: Color) -> String {
+    match colour {
+        Color::TrueColor { r, g, b } => format!("48;2;{r};{g};{b}"),
+        basic => basic.to_bg_str().into_owned(),
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn test_layers() {
+        let input = std::fs::read_to_string("src/bin/day6/sample.txt").expect("File not found");
+        let lab = input.parse::<Field<char>>().expect("Doesn't error");
+        let path = (0..6).map(|y| (Location(4, y), '↑'));
+
+        let renderer = Renderer::new(&lab)
+            .glyphs("path", path)
+            .locations(
+                "obstacle",
+                [Location(3, 6)],
+                CellStyle::glyph('O').fg(Color::Red),
+            )
+            .colours("start", [(Location(4, 6), Color::Green)]);
+
+        let plain = renderer.render(Output::Plain);
+        let mut lines = plain.lines();
+        assert_eq!(lines.next(), Some("....↑....."));
+        assert_eq!(lines.nth(5), Some(".#.O^....."));
+        assert_eq!(
+            plain.lines().skip(10).collect::<Vec<_>>(),
+            ["  path", "O obstacle", "  start"]
+        );
+        assert!(!plain.contains('\x1B'));
+
+        let ansi = renderer.render(Output::Ansi);
+        assert!(ansi.contains("\x1B[31mO\x1B[0m"));
+        assert!(ansi.contains("\x1B[42m^\x1B[0m"));
+        assert_eq!(
+            ansi.lines().nth(6).map(|l| l
+                .replace("\x1B[31m", "")
+                .replace("\x1B[42m", "")
+                .replace("\x1B[0m", "")),
+            Some(".#.O^.....".to_string())
+        );
+    }
+
+    #[test]
+    fn test_true_colour() {
+        let field = "ab".parse::<Field<char>>().expect("Doesn't error");
+        let out = Renderer::with_style(&field, |&c| match c {
+            'a' => CellStyle::glyph(c).bg(Color::TrueColor { r: 1, g: 2, b: 3 }),
+            _ => CellStyle::glyph(c)
+                .fg(Color::TrueColor {
+                    r: 255,
+                    g: 255,
+                    b: 0,
+                })
+                .bg(Color::Blue),
+        })
+        .render(Output::Ansi);
+        assert_eq!(
+            out,
+            "\x1B[48;2;1;2;3ma\x1B[0m\x1B[38;2;255;255;0;44mb\x1B[0m\n"
+        );
+    }
+
+    #[test]
+    fn test_cell_width() {
+        let field = "12\n34".parse::<Field<u8>>().expect("Doesn't error");
+        let out = Renderer::with_style(&field, |&v| CellStyle::glyph(v * 2))
+            .cell_width(3)
+            .render(Output::Plain);
+        assert_eq!(out, "2  4  \n6  8  \n");
+
+        // wide glyphs fill both columns without extra padding
+        let out = Renderer::new(&field)
+            .cell_width(2)
+            .glyphs("start", [(Location(0, 0), '🚀')])
+            .render(Output::Plain);
+        assert_eq!(out.lines().take(2).collect::<Vec<_>>(), ["🚀2 ", "3 4 "]);
+    }
+}
@@ -0,0 +1,353 @@
+use crate::field::Field;
+use crate::location::{Connectivity, Location};
+use std::{
+    cmp::Reverse,
+    collections::{BinaryHeap, VecDeque},
+    fmt::Debug,
+};
+
+/// Outcome of a successful search from `start` to the first goal reached
+pub struct SearchResult {
+    pub cost: usize,
+    pub path: Vec<Location>,
+    start: Location,
+    // every predecessor lying on a shortest path to each settled location
+    preds: Field<Vec<Location>>,
+}
+
+impl SearchResult {
+    fn new(start: Location, goal: Location, cost: usize, preds: Field<Vec<Location>>) -> Self {
+        // follow the first predecessor of each location back to the start
+        let mut path = std::iter::successors(Some(goal), |&l| {
+            (l != start).then(|| preds.get(l).and_then(|p| p.first().copied()))?
+        })
+        .collect::<Vec<_>>();
+        path.reverse();
+        SearchResult {
+            cost,
+            path,
+            start,
+            preds,
+        }
+    }
+
+    pub fn goal(&self) -> Location {
+        *self
+            .path
+            .last()
+            .expect("path holds at least the start location")
+    }
+
+    // every shortest path from start to goal;
+    // their number can grow exponentially on open grids
+    pub fn all_paths(&self) -> Vec<Vec<Location>> {
+        let mut paths = Vec::new();
+        let mut path = vec![self.goal()];
+        self.collect_paths(&mut path, &mut paths);
+        paths
+    }
+
+    fn collect_paths(&self, path: &mut Vec<Location>, paths: &mut Vec<Vec<Location>>) {
+        let &last = path.last().unwrap();
+        if last == self.start {
+            paths.push(path.iter().rev().copied().collect());
+            return;
+        }
+        for &p in self.preds.get(last).into_iter().flatten() {
+            path.push(p);
+            self.collect_paths(path, paths);
+            path.pop();
+        }
+    }
+}
+
+impl Debug for SearchResult {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("SearchResult")
+            .field("cost", &self.cost)
+            .field("path", &self.path)
+            .finish()
+    }
+}
+
+// breadth first search where every step costs 1
+// passable(from, to) decides whether a step between two adjacent cells is allowed
+pub fn bfs<T>(
+    field: &Field<T>,
+    start: Location,
+    passable: impl Fn(&T, &T) -> bool,
+    is_goal: impl Fn(Location, &T) -> bool,
+) -> Option<SearchResult> {
+    let mut dist = Field::new(field.width(), field.height(), None);
+    let mut preds = Field::new(field.width(), field.height(), Vec::new());
+    let mut queue = VecDeque::from([start]);
+    *dist.get_mut(start)? = Some(0);
+
+    while let Some(loc) = queue.pop_front() {
+        let d = dist.get(loc).copied().flatten()?;
+        let val = field.get(loc)?;
+        if is_goal(loc, val) {
+            return Some(SearchResult::new(start, loc, d, preds));
+        }
+        for (nl, _, nv) in field.neighbours4(loc) {
+            if !passable(val, nv) {
+                continue;
+            }
+            match dist.get(nl).copied().flatten() {
+                None => {
+                    *dist.get_mut(nl)? = Some(d + 1);
+                    preds.get_mut(nl)?.push(loc);
+                    queue.push_back(nl);
+                }
+                Some(nd) if nd == d + 1 => preds.get_mut(nl)?.push(loc),
+                _ => (),
+            }
+        }
+    }
+    None
+}
+
+// lowest cost search; step_cost(from, to) is expected to be positive
+pub fn dijkstra<T>(
+    field: &Field<T>,
+    start: Location,
+    passable: impl Fn(&T, &T) -> bool,
+    step_cost: impl Fn(&T, &T) -> usize,
+    is_goal: impl Fn(Location, &T) -> bool,
+) -> Option<SearchResult> {
+    astar(field, start, passable, step_cost, is_goal, |_| 0)
+}
+
+// lowest cost search guided by heuristic(location), an estimate of the remaining cost
+// the heuristic must be consistent, h(a) <= step_cost(a, b) + h(b) for every step, as settled
+// locations are never reopened; otherwise costs and all_paths() may be off
+pub fn astar<T>(
+    field: &Field<T>,
+    start: Location,
+    passable: impl Fn(&T, &T) -> bool,
+    step_cost: impl Fn(&T, &T) -> usize,
+    is_goal: impl Fn(Location, &T) -> bool,
+    heuristic: impl Fn(Location) -> usize,
+) -> Option<SearchResult> {
+    let mut dist = Field::new(field.width(), field.height(), None);
+    let mut settled = Field::new(field.width(), field.height(), false);
+    let mut preds = Field::new(field.width(), field.height(), Vec::new());
+    // ordering on (estimate, cost) settles cheaper locations first on equal estimates,
+    // so all predecessors of a location are known by the time it is settled
+    let mut heap = BinaryHeap::from([Reverse((heuristic(start), 0, start))]);
+    *dist.get_mut(start)? = Some(0);
+
+    while let Some(Reverse((_, d, loc))) = heap.pop() {
+        if std::mem::replace(settled.get_mut(loc)?, true) {
+            continue;
+        }
+        let val = field.get(loc)?;
+        if is_goal(loc, val) {
+            return Some(SearchResult::new(start, loc, d, preds));
+        }
+        for (nl, _, nv) in field.neighbours4(loc) {
+            if !passable(val, nv) || settled.get(nl) == Some(&true) {
+                continue;
+            }
+            let nd = d + step_cost(val, nv);
+            match dist.get(nl).copied().flatten() {
+                Some(old) if old < nd => continue,
+                Some(old) if old == nd => preds.get_mut(nl)?.push(loc),
+                _ => {
+                    *dist.get_mut(nl)? = Some(nd);
+                    *preds.get_mut(nl)? = vec![loc];
+                    heap.push(Reverse((nd + heuristic(nl), nd, nl)));
+                }
+            }
+        }
+    }
+    None
+}
+
+impl<T> Field<T> {
+    // steps from every cell to the nearest source, None where no source can be reached;
+    // Manhattan distances with Connectivity::Four, Chebyshev ones with Connectivity::Eight.
+    // passable(cell) decides whether a cell can be stepped onto, sources are always reached
+    pub fn distance_field(
+        &self,
+        sources: impl IntoIterator<Item = Location>,
+        connectivity: Connectivity,
+        passable: impl Fn(&T) -> bool,
+    ) -> Field<Option<usize>> {
+        let nearest = self.nearest_source(sources, connectivity, passable);
+        Field::from_fn(self.width(), self.height(), |l| {
+            nearest.get(l).copied().flatten().map(|(_, d)| d)
+        })
+    }
+
+    // as distance_field() but also tells which source each cell is closest to,
+    // partitioning the field into regions around the sources.
+    // Ties go to the source listed first
+    pub fn nearest_source(
+        &self,
+        sources: impl IntoIterator<Item = Location>,
+        connectivity: Connectivity,
+        passable: impl Fn(&T) -> bool,
+    ) -> Field<Option<(Location, usize)>> {
+        let mut nearest = Field::new(self.width(), self.height(), None);
+        // the queue stays ordered by distance then by source,
+        // so every cell is first reached from the earliest listed of its nearest sources
+        let mut queue = VecDeque::new();
+        for s in sources {
+            if let Some(cell @ None) = nearest.get_mut(s) {
+                *cell = Some((s, 0));
+                queue.push_back(s);
+            }
+        }
+
+        while let Some(loc) = queue.pop_front() {
+            let Some((source, d)) = nearest.get(loc).copied().flatten() else {
+                continue;
+            };
+            for (nl, _, nv) in self.neighbours(loc, connectivity.stencil()) {
+                if !passable(nv) {
+                    continue;
+                }
+                if let Some(cell @ None) = nearest.get_mut(nl) {
+                    *cell = Some((source, d + 1));
+                    queue.push_back(nl);
+                }
+            }
+        }
+        nearest
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    fn is_valid_path<T>(
+        field: &Field<T>,
+        path: &[Location],
+        passable: impl Fn(&T, &T) -> bool,
+    ) -> bool {
+        path.windows(2).all(|w| {
+            let (dx, dy) = w[0].distance(&w[1]);
+            dx + dy == 1 && passable(field.get(w[0]).unwrap(), field.get(w[1]).unwrap())
+        })
+    }
+
+    #[test]
+    fn test_lab_paths() {
+        let input = std::fs::read_to_string("src/bin/day6/sample.txt").expect("File not found");
+        let lab = input.parse::<Field<char>>().expect("Doesn't error");
+        let (start, goal) = (Location(4, 6), Location(9, 0));
+        let passable = |_: &char, &to: &char| to != '#';
+
+        let b = bfs(&lab, start, passable, |l, _| l == goal).expect("path exists");
+        let d = dijkstra(&lab, start, passable, |_, _| 1, |l, _| l == goal).expect("path exists");
+        let a = astar(
+            &lab,
+            start,
+            passable,
+            |_, _| 1,
+            |l, _| l == goal,
+            |l| {
+                let (dx, dy) = l.distance(&goal);
+                dx + dy
+            },
+        )
+        .expect("path exists");
+
+        assert_eq!(b.cost, 11);
+        assert_eq!((b.cost, b.cost), (d.cost, a.cost));
+        for r in [&b, &d, &a] {
+            assert_eq!(r.path.first(), Some(&start));
+            assert_eq!(r.goal(), goal);
+            assert_eq!(r.path.len(), r.cost + 1);
+            assert!(is_valid_path(&lab, &r.path, passable));
+        }
+        assert_eq!(b.all_paths().len(), d.all_paths().len());
+        assert_eq!(b.all_paths().len(), a.all_paths().len());
+        assert!(bfs(&lab, start, passable, |l, _| l == Location(10, 0)).is_none());
+    }
+
+    #[test]
+    fn test_trail_paths() {
+        let input = std::fs::read_to_string("src/bin/day10/sample.txt").expect("File not found");
+        let map = input.parse::<Field<u8>>().expect("Doesn't error");
+        let passable = |&from: &u8, &to: &u8| to == from + 1;
+
+        let r = bfs(&map, Location(2, 0), passable, |_, &h| h == 9).expect("trail exists");
+        assert_eq!(r.cost, 9);
+        let paths = r.all_paths();
+        assert!(
+            paths
+                .iter()
+                .all(|p| p.len() == 10 && is_valid_path(&map, p, passable))
+        );
+        assert!(paths.contains(&r.path));
+
+        // climbing costs more than the height difference alone
+        let r = dijkstra(
+            &map,
+            Location(2, 0),
+            |_, _| true,
+            |&f, &t| 1 + f.abs_diff(t) as usize,
+            |l, _| l == Location(7, 7),
+        )
+        .expect("path exists");
+        assert!(r.cost >= 14);
+        assert_eq!(r.path.len(), r.all_paths()[0].len());
+    }
+
+    #[test]
+    fn test_distance_field() {
+        let input = std::fs::read_to_string("src/bin/day10/sample.txt").expect("File not found");
+        let map = input.parse::<Field<u8>>().expect("Doesn't error");
+        let peaks = map.positions(|&h| h == 9).collect::<Vec<_>>();
+
+        // on open ground the distance is the closest peak by either metric
+        for (connectivity, metric) in [
+            (
+                Connectivity::Four,
+                (|(dx, dy)| dx + dy) as fn((usize, usize)) -> usize,
+            ),
+            (Connectivity::Eight, |(dx, dy)| dx.max(dy)),
+        ] {
+            let dist = map.distance_field(peaks.iter().copied(), connectivity, |_| true);
+            assert!(
+                dist.iter_locations()
+                    .all(|(l, &d)| { d == peaks.iter().map(|p| metric(l.distance(p))).min() })
+            );
+        }
+
+        // nothing can be stepped onto but the sources themselves
+        let dist = map.distance_field(peaks.iter().copied(), Connectivity::Four, |_| false);
+        assert_eq!(dist.iter().flatten().count(), peaks.len());
+        assert!(
+            map.distance_field([], Connectivity::Four, |_| true)
+                .iter()
+                .all(Option::is_none)
+        );
+    }
+
+    #[test]
+    fn test_nearest_source() {
+        let input = std::fs::read_to_string("src/bin/day6/sample.txt").expect("File not found");
+        let lab = input.parse::<Field<char>>().expect("Doesn't error");
+        let (a, b) = (Location(0, 0), Location(9, 9));
+
+        let regions = lab.nearest_source([a, b], Connectivity::Four, |&c| c != '#');
+        assert_eq!(regions.get(a), Some(&Some((a, 0))));
+        assert_eq!(regions.get(Location(9, 0)), Some(&Some((a, 11))));
+        assert_eq!(regions.get(Location(8, 8)), Some(&Some((b, 2))));
+        // equally far from both, the first source wins
+        assert_eq!(regions.get(Location(4, 5)), Some(&Some((a, 9))));
+        assert_eq!(regions.get(Location(4, 0)), Some(&None));
+        assert!(
+            regions
+                .iter_locations()
+                .all(|(l, r)| r.is_some() == (lab.get(l) != Some(&'#')))
+        );
+
+        let swapped = lab.nearest_source([b, a], Connectivity::Four, |&c| c != '#');
+        assert_eq!(swapped.get(Location(4, 5)), Some(&Some((b, 9))));
+    }
+}
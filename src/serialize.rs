@@ -0,0 +1,98 @@
+use crate::field::Field;
+use serde::{Deserialize, Deserializer, Serialize, Serializer, de::Error};
+
+// fields are stored as their dimensions followed by the cells in row-major order,
+// i.e. {"width":2,"height":1,"cells":[1,2]}
+#[derive(Serialize)]
+struct FieldRef<'a, T> {
+    width: usize,
+    height: usize,
+    cells: &'a [T],
+}
+
+#[derive(Deserialize)]
+struct FieldData<T> {
+    width: usize,
+    height: usize,
+    cells: Vec<T>,
+}
+
+impl<T: Serialize> Serialize for Field<T> {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        FieldRef {
+            width: self.width(),
+            height: self.height(),
+            cells: self.as_slice(),
+        }
+        .serialize(serializer)
+    }
+}
+
+impl<'de, T: Deserialize<'de>> Deserialize<'de> for Field<T> {
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        let FieldData {
+            width,
+            height,
+            cells,
+        } = FieldData::deserialize(deserializer)?;
+        if width.checked_mul(height) != Some(cells.len()) {
+            return Err(D::Error::custom(format!(
+                "expected {width}x{height} cells but found {}",
+                cells.len()
+            )));
+        }
+        Ok(Field::from_cells(cells, width, height))
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::location::{Direction, Location};
+
+    #[test]
+    fn test_json_round_trip() {
+        let input = std::fs::read_to_string("src/bin/day10/sample.txt").expect("File not found");
+        let map = input.parse::<Field<u8>>().expect("Doesn't error");
+
+        let json = serde_json::to_string(&map).expect("serialises");
+        let back = serde_json::from_str::<Field<u8>>(&json).expect("deserialises");
+        assert_eq!((back.width(), back.height()), (8, 8));
+        assert_eq!(back.as_slice(), map.as_slice());
+
+        let field = "ab\ncd".parse::<Field<char>>().expect("Doesn't error");
+        let json = serde_json::to_string(&field).expect("serialises");
+        assert_eq!(json, r#"{"width":2,"height":2,"cells":["a","b","c","d"]}"#);
+
+        let empty = serde_json::from_str::<Field<char>>(r#"{"width":0,"height":0,"cells":[]}"#)
+            .expect("deserialises");
+        assert_eq!((empty.width(), empty.height()), (0, 0));
+        let json = serde_json::to_string(&Field::new(0, 3, 'x')).expect("serialises");
+        assert_eq!(json, r#"{"width":0,"height":3,"cells":[]}"#);
+        let back = serde_json::from_str::<Field<char>>(&json).expect("deserialises");
+        assert_eq!((back.width(), back.height()), (0, 3));
+
+        let l = Location(3, 7);
+        let json = serde_json::to_string(&l).expect("serialises");
+        assert_eq!(json, "[3,7]");
+        assert_eq!(serde_json::from_str::<Location>(&json).ok(), Some(l));
+
+        for d in [(-1, 0), (0, 1), (1, 0), (0, -1)] {
+            let json = serde_json::to_string(&Direction::from(d)).expect("serialises");
+            let back = serde_json::from_str::<Direction>(&json).expect("deserialises");
+            assert_eq!(back.to_cartesian(), d);
+        }
+        assert_eq!(
+            serde_json::to_string(&Direction::Up).expect("serialises"),
+            r#""Up""#
+        );
+    }
+
+    #[test]
+    fn test_invalid_field() {
+        let err = serde_json::from_str::<Field<u8>>(r#"{"width":2,"height":2,"cells":[1,2,3]}"#)
+            .expect_err("too few cells");
+        assert!(err.to_string().contains("expected 2x2 cells but found 3"));
+        assert!(serde_json::from_str::<Field<u8>>(r#"{"width":2,"cells":[1,2]}"#).is_err());
+    }
+}
@@ -0,0 +1,232 @@
+use crate::location::{DirVector, Location};
+use std::num::TryFromIntError;
+use std::ops::{Add, AddAssign, Div, Mul, Neg, Sub, SubAssign};
+
+/// Signed displacement (dx, dy) with y growing downwards, as with `DirVector`
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq, Hash, PartialOrd, Ord)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct Vector(pub isize, pub isize);
+
+/// Signed position (x, y); unlike `Location` it can lie left of or above the origin
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq, Hash, PartialOrd, Ord)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct Point(pub isize, pub isize);
+
+impl Vector {
+    pub const ZERO: Vector = Vector(0, 0);
+
+    // displacement taking one location to the other;
+    // exact as long as the difference fits in an isize, i.e. any two cells of a field
+    pub fn between(from: Location, to: Location) -> Vector {
+        Vector(
+            to.0.wrapping_sub(from.0) as isize,
+            to.1.wrapping_sub(from.1) as isize,
+        )
+    }
+    // number of orthogonal steps to cover the displacement
+    pub fn manhattan(&self) -> usize {
+        self.0.unsigned_abs() + self.1.unsigned_abs()
+    }
+    // number of steps to cover the displacement when diagonal steps are allowed
+    pub fn chebyshev(&self) -> usize {
+        self.0.unsigned_abs().max(self.1.unsigned_abs())
+    }
+}
+
+impl From<DirVector> for Vector {
+    fn from((dx, dy): DirVector) -> Self {
+        Vector(dx, dy)
+    }
+}
+
+impl From<Vector> for DirVector {
+    fn from(Vector(dx, dy): Vector) -> Self {
+        (dx, dy)
+    }
+}
+
+impl Add for Vector {
+    type Output = Vector;
+
+    fn add(self, rhs: Vector) -> Vector {
+        Vector(self.0 + rhs.0, self.1 + rhs.1)
+    }
+}
+
+impl Sub for Vector {
+    type Output = Vector;
+
+    fn sub(self, rhs: Vector) -> Vector {
+        Vector(self.0 - rhs.0, self.1 - rhs.1)
+    }
+}
+
+impl Neg for Vector {
+    type Output = Vector;
+
+    fn neg(self) -> Vector {
+        Vector(-self.0, -self.1)
+    }
+}
+
+impl Mul<isize> for Vector {
+    type Output = Vector;
+
+    fn mul(self, rhs: isize) -> Vector {
+        Vector(self.0 * rhs, self.1 * rhs)
+    }
+}
+
+// rounds towards zero, as with integer division
+impl Div<isize> for Vector {
+    type Output = Vector;
+
+    fn div(self, rhs: isize) -> Vector {
+        Vector(self.0 / rhs, self.1 / rhs)
+    }
+}
+
+impl AddAssign for Vector {
+    fn add_assign(&mut self, rhs: Vector) {
+        *self = *self + rhs;
+    }
+}
+
+impl SubAssign for Vector {
+    fn sub_assign(&mut self, rhs: Vector) {
+        *self = *self - rhs;
+    }
+}
+
+impl Add<Vector> for Point {
+    type Output = Point;
+
+    fn add(self, rhs: Vector) -> Point {
+        Point(self.0 + rhs.0, self.1 + rhs.1)
+    }
+}
+
+impl Sub<Vector> for Point {
+    type Output = Point;
+
+    fn sub(self, rhs: Vector) -> Point {
+        Point(self.0 - rhs.0, self.1 - rhs.1)
+    }
+}
+
+// displacement from rhs to self
+impl Sub for Point {
+    type Output = Vector;
+
+    fn sub(self, rhs: Point) -> Vector {
+        Vector(self.0 - rhs.0, self.1 - rhs.1)
+    }
+}
+
+impl AddAssign<Vector> for Point {
+    fn add_assign(&mut self, rhs: Vector) {
+        *self = *self + rhs;
+    }
+}
+
+impl SubAssign<Vector> for Point {
+    fn sub_assign(&mut self, rhs: Vector) {
+        *self = *self - rhs;
+    }
+}
+
+// fails for locations beyond isize::MAX
+impl TryFrom<Location> for Point {
+    type Error = TryFromIntError;
+
+    fn try_from(Location(x, y): Location) -> Result<Self, Self::Error> {
+        Ok(Point(x.try_into()?, y.try_into()?))
+    }
+}
+
+// fails for points left of or above the origin
+impl TryFrom<Point> for Location {
+    type Error = TryFromIntError;
+
+    fn try_from(Point(x, y): Point) -> Result<Self, Self::Error> {
+        Ok(Location(x.try_into()?, y.try_into()?))
+    }
+}
+
+// None when the result would fall left of or above the origin, as with move_relative()
+impl Add<Vector> for Location {
+    type Output = Option<Location>;
+
+    fn add(self, rhs: Vector) -> Option<Location> {
+        self.move_relative(rhs)
+    }
+}
+
+impl Sub<Vector> for Location {
+    type Output = Option<Location>;
+
+    // subtracts directly, as negating isize::MIN would overflow
+    fn sub(self, Vector(dx, dy): Vector) -> Option<Location> {
+        let x = self.0.checked_sub_signed(dx);
+        let y = self.1.checked_sub_signed(dy);
+        x.zip(y).map(|(x, y)| Location(x, y))
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::location::{Direction, turn_ccw, turn_cw};
+
+    #[test]
+    fn test_vector_maths() {
+        let v = Vector(2, -1);
+        assert_eq!(v + Vector(1, 1), Vector(3, 0));
+        assert_eq!(v - Vector(1, 1), Vector(1, -2));
+        assert_eq!(-v, Vector(-2, 1));
+        assert_eq!(v * 3, Vector(6, -3));
+        assert_eq!(Vector(7, -7) / 2, Vector(3, -3));
+        assert_eq!((v.manhattan(), v.chebyshev()), (3, 2));
+
+        let mut p = Point(0, 0);
+        p -= v;
+        assert_eq!(p, Point(-2, 1));
+        p += v * 2;
+        assert_eq!(p - Point(0, 0), v);
+        assert!(Location::try_from(p).is_err());
+        assert_eq!(Location::try_from(Point(2, 1)), Ok(Location(2, 1)));
+        assert_eq!(Point::try_from(Location(4, 5)), Ok(Point(4, 5)));
+        assert!(Point::try_from(Location(usize::MAX, 0)).is_err());
+
+        assert_eq!(
+            Vector::between(Location(4, 3), Location(5, 5)),
+            Vector(1, 2)
+        );
+        assert_eq!(
+            Vector::between(Location(8, 4), Location(5, 5)),
+            Vector(-3, 1)
+        );
+        assert_eq!(Location(1, 1) + Vector(1, 1), Some(Location(2, 2)));
+        assert_eq!(Location(1, 1) - Vector(2, 0), None);
+        assert_eq!(
+            Location(5, 5) - Vector(isize::MIN, 0),
+            Some(Location(5 + (1 << 63), 5))
+        );
+        assert_eq!(Location(5, 5) - Vector(isize::MAX, 0), None);
+        assert_eq!(Location(5, 5) - Vector(0, -3), Some(Location(5, 8)));
+    }
+
+    #[test]
+    fn test_dirvector_compatibility() {
+        assert_eq!(
+            Location(1, 1).move_relative(Vector(0, -1)),
+            Some(Location(1, 0))
+        );
+        assert_eq!(turn_cw(Vector(0, -1)), Vector(1, 0));
+        assert_eq!(turn_ccw(Vector(0, -1)), Vector(-1, 0));
+        assert_eq!(turn_cw((0, -1)), (1, 0));
+        assert_eq!(Vector::from(Direction::Down), Vector(0, 1));
+        assert_eq!(Direction::from(Vector(-1, 0)).to_cartesian(), (-1, 0));
+        assert_eq!(DirVector::from(Vector(3, 4)), (3, 4));
+    }
+}
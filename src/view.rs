@@ -0,0 +1,218 @@
+use crate::field::Field;
+use crate::location::{DirVector, Location};
+
+/// A borrowed window onto a `Field` seen through any combination of
+/// rotations, reflections and crops, without copying a single cell.
+///
+/// A view location `p` maps onto the field location `origin + axes * p`
+pub struct FieldView<'a, T> {
+    field: &'a Field<T>,
+    origin: (isize, isize),
+    // columns of the 2x2 matrix: where one step along view x / view y lands on the field
+    axes: [DirVector; 2],
+    width: usize,
+    height: usize,
+}
+
+impl<T> Clone for FieldView<'_, T> {
+    fn clone(&self) -> Self {
+        *self
+    }
+}
+
+impl<T> Copy for FieldView<'_, T> {}
+
+impl<T> Field<T> {
+    pub fn view(&self) -> FieldView<'_, T> {
+        FieldView {
+            field: self,
+            origin: (0, 0),
+            axes: [(1, 0), (0, 1)],
+            width: self.width(),
+            height: self.height(),
+        }
+    }
+}
+
+impl<'a, T> FieldView<'a, T> {
+    pub fn get(&self, l: Location) -> Option<&'a T> {
+        self.to_field(l).and_then(|l| self.field.get(l))
+    }
+    pub fn peek(&self, l: Location, d: DirVector) -> Option<&'a T> {
+        l.move_relative(d).and_then(|l| self.get(l))
+    }
+    pub fn within_bounds(&self, l: Location) -> bool {
+        l.0 < self.width && l.1 < self.height
+    }
+    pub fn iter(&self) -> impl Iterator<Item = &'a T> {
+        let view = *self;
+        (0..self.height)
+            .flat_map(move |y| (0..view.width).map(move |x| Location(x, y)))
+            .filter_map(move |l| view.get(l))
+    }
+    pub fn width(&self) -> usize {
+        self.width
+    }
+    pub fn height(&self) -> usize {
+        self.height
+    }
+    // the location on the underlying field a view location refers to
+    pub fn to_field(&self, l: Location) -> Option<Location> {
+        if !self.within_bounds(l) {
+            return None;
+        }
+        let (x, y) = (l.0 as isize, l.1 as isize);
+        let [(ax, ay), (bx, by)] = self.axes;
+        let fx = self.origin.0 + ax * x + bx * y;
+        let fy = self.origin.1 + ay * x + by * y;
+        Some(Location(fx as usize, fy as usize))
+    }
+    // materialise the view into a new field
+    pub fn to_owned(&self) -> Field<T>
+    where
+        T: Clone,
+    {
+        Field::from_fn(self.width, self.height, |l| self.get(l).unwrap().clone())
+    }
+
+    // new view whose location p maps onto this view's location (t + m * p)
+    fn compose(&self, t: (isize, isize), m: [DirVector; 2], width: usize, height: usize) -> Self {
+        let [(ax, ay), (bx, by)] = self.axes;
+        let apply = |(x, y): (isize, isize)| (ax * x + bx * y, ay * x + by * y);
+        let (tx, ty) = apply(t);
+        FieldView {
+            field: self.field,
+            origin: (self.origin.0 + tx, self.origin.1 + ty),
+            axes: [apply(m[0]), apply(m[1])],
+            width,
+            height,
+        }
+    }
+    fn last(&self) -> (isize, isize) {
+        (self.width as isize - 1, self.height as isize - 1)
+    }
+
+    // rotate clockwise by 90 degrees
+    pub fn rotate90(&self) -> Self {
+        self.compose(
+            (0, self.last().1),
+            [(0, -1), (1, 0)],
+            self.height,
+            self.width,
+        )
+    }
+    pub fn rotate180(&self) -> Self {
+        self.compose(self.last(), [(-1, 0), (0, -1)], self.width, self.height)
+    }
+    // rotate clockwise by 270 degrees, i.e. counter clockwise by 90 degrees
+    pub fn rotate270(&self) -> Self {
+        self.compose(
+            (self.last().0, 0),
+            [(0, 1), (-1, 0)],
+            self.height,
+            self.width,
+        )
+    }
+    // swap rows with columns, mirroring along the main diagonal
+    pub fn transpose(&self) -> Self {
+        self.compose((0, 0), [(0, 1), (1, 0)], self.height, self.width)
+    }
+    // mirror left to right
+    pub fn flip_horizontal(&self) -> Self {
+        self.compose(
+            (self.last().0, 0),
+            [(-1, 0), (0, 1)],
+            self.width,
+            self.height,
+        )
+    }
+    // mirror top to bottom
+    pub fn flip_vertical(&self) -> Self {
+        self.compose(
+            (0, self.last().1),
+            [(1, 0), (0, -1)],
+            self.width,
+            self.height,
+        )
+    }
+    // window of up to width x height cells starting at top_left;
+    // the window is clipped to the view's bounds
+    pub fn crop(&self, top_left: Location, width: usize, height: usize) -> Self {
+        let width = width.min(self.width.saturating_sub(top_left.0));
+        let height = height.min(self.height.saturating_sub(top_left.1));
+        self.compose(
+            (top_left.0 as isize, top_left.1 as isize),
+            [(1, 0), (0, 1)],
+            width,
+            height,
+        )
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    fn to_string(field: &Field<char>) -> String {
+        field
+            .rows()
+            .map(|r| r.iter().collect::<String>())
+            .collect::<Vec<_>>()
+            .join("\n")
+    }
+
+    #[test]
+    fn test_transforms() {
+        let field = "abc\ndef".parse::<Field<char>>().expect("Doesn't error");
+        let view = field.view();
+
+        assert_eq!(to_string(&view.to_owned()), "abc\ndef");
+        assert_eq!(to_string(&view.rotate90().to_owned()), "da\neb\nfc");
+        assert_eq!(to_string(&view.rotate180().to_owned()), "fed\ncba");
+        assert_eq!(to_string(&view.rotate270().to_owned()), "cf\nbe\nad");
+        assert_eq!(to_string(&view.transpose().to_owned()), "ad\nbe\ncf");
+        assert_eq!(to_string(&view.flip_horizontal().to_owned()), "cba\nfed");
+        assert_eq!(to_string(&view.flip_vertical().to_owned()), "def\nabc");
+        assert_eq!(to_string(&view.crop(Location(1, 0), 5, 1).to_owned()), "bc");
+        // composed transforms
+        assert_eq!(
+            to_string(&view.rotate90().rotate90().rotate90().rotate90().to_owned()),
+            "abc\ndef"
+        );
+        assert_eq!(
+            to_string(&view.rotate90().flip_horizontal().to_owned()),
+            to_string(&view.transpose().to_owned())
+        );
+        assert_eq!(
+            to_string(&view.rotate180().crop(Location(1, 1), 2, 2).to_owned()),
+            "ba"
+        );
+
+        let r = view.rotate90();
+        assert_eq!((r.width(), r.height()), (2, 3));
+        assert_eq!(r.get(Location(1, 0)), Some(&'a'));
+        assert_eq!(r.peek(Location(1, 0), (-1, 2)), Some(&'f'));
+        assert_eq!(r.get(Location(2, 0)), None);
+        assert_eq!(r.to_field(Location(0, 2)), Some(Location(2, 1)));
+    }
+
+    #[test]
+    fn test_rotated_word_search() {
+        let input = std::fs::read_to_string("src/bin/day4/sample.txt").expect("File not found");
+        let field = input.parse::<Field<char>>().expect("Doesn't error");
+
+        // every horizontal or vertical occurrence, in any reading direction,
+        // shows up as a left-to-right occurrence in one of the four rotations
+        let view = field.view();
+        let count = [view, view.rotate90(), view.rotate180(), view.rotate270()]
+            .iter()
+            .map(|v| {
+                let text = v.to_owned();
+                text.rows()
+                    .map(|r| r.iter().collect::<String>().matches("XMAS").count())
+                    .sum::<usize>()
+            })
+            .sum::<usize>();
+        assert_eq!(count, 8);
+    }
+}